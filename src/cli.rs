@@ -0,0 +1,842 @@
+//! Command-line options for the demo.
+//!
+//! Parsing is hand-rolled on purpose: the demo has no dependencies so it can be
+//! pasted straight into the Rust Playground, and a handful of flags doesn't
+//! justify pulling in an argument-parsing crate.
+
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::color::ColorScheme;
+use crate::{Consumer, Endian, FillPattern, ViewMode, BUFFER_SIZE, GUARD_SENTINEL, MAX_BUFFER_SIZE};
+
+/// Usage text printed on `--help` or after a parse error.
+pub const USAGE: &str = "\
+Usage: rust-uaf [OPTIONS]
+
+Options:
+  --scenario <NAME>       Run a single scenario (or `all`) instead of the default demo
+  --list-scenarios        List the scenarios with a one-line description, then exit
+  --frames <DIR>          Write one image per write step into DIR (frame_000.ppm, ...)
+  --frame-format <FMT>    Image format for --frames: ppm (default) or png
+  --history-export <PATH> Write every printed snapshot as one JSON object per line to PATH
+  --html-anim <PATH>      Write a self-contained HTML page that animates every snapshot
+  --tikz <PATH>           After the run, write the layout and final corruption as a TikZ figure
+  --len-from-env <VAR>    Start every frame with len read from environment variable VAR
+  --load <PATH>           Copy a Frame's bytes from PATH over a fresh Frame and run safe code on them
+  --dump <PATH>           After the run, write the last test's Frame bytes to PATH (for --load)
+  --save-trace <PATH>     After the run, write every write and snapshot to PATH as JSON (for --replay)
+  --replay <PATH>         Re-render a --save-trace file from its snapshots, with no out-of-bounds writes
+  --simulate              Model the demo on a plain byte array in safe Rust (no unsafe, no UB)
+  --miri-safe             Same as --simulate; picked automatically when running under Miri
+  --sweep <RANGE>         Run every write length in RANGE (e.g. 0..=16) and print an outcome table
+  --writes <LENS>         Write lengths to run, comma-separated (default 5,6,8,10,12)
+  --direction <DIR>       forward (default: buffer[0] upward) or reverse (the highest offset first,
+                          down toward the buffer)
+  --fill <PATTERN>        Overflow bytes: index (0, 1, 2, ...; default), const:NN (NN in hex, e.g. const:ff),
+                          ascii (A, B, C, ...), or random (seeded, see --seed)
+  --fill-value <HEX>      Write these hex bytes (41 or 0x41; comma-separated, cycled) instead of 0, 1, 2, ...
+  --target-num <VALUE>    Overflow just far enough to set num to VALUE, writing len's bytes back unchanged
+  --buffer-size <N>       Give the default demo's Frame an N-byte buffer (1 to 16, default 5)
+  --location <WHERE>      Put each test's Frame on the stack (default) or in a Box on the heap
+  --consumer <FN>         Safe code to run on the corrupted frame: sum (safe_sum_prefix, default),
+                          max (safe_max_byte), or copy (safe_copy_out)
+  --highlight-reads       Show each byte safe_sum_prefix reads, up to the one that panics
+  --no-catch              Let safe_sum_prefix's panic abort the run for real (try RUST_BACKTRACE=1)
+  --delay <MS>            Sleep MS milliseconds after each printed row (animation)
+  --dramatic              With --delay, only pause on writes that land in len, num, or guard
+  --step                  On a terminal, wait for Enter after each write (q stops the test)
+  --changes-only          Skip rows where no byte changed, noting how many writes were skipped
+  --jsonl                 Stream one JSON object per event to stdout instead of the text dump
+  --quiet                 Print only one line per test (its write length and whether safe code
+                          panicked) instead of the dump; the exit code still reports the worst outcome
+  --format <FMT>          Output format: text (default), json for one object per test,
+                          html for the colored dump as a web page, svg for the last
+                          test's final bytes as a labeled grid, or csv for one row of
+                          byte values per write
+  --html-fragment         With --format html, print just the <pre> instead of a whole document
+  --random-init           Start each test with random buffer and num (len stays valid)
+  --random-guard          With --random-init, randomize the guard too
+  --guard <VALUE>         Start the guard at VALUE instead of 0xDEADBEEF (e.g. 0xCAFEBABE)
+  --random-fill           Same as --fill random
+  --seed <N>              Seed for everything random (decimal or 0x hex)
+  --break-on-offset <N>   Report (and on a terminal, pause) whenever byte N of the Frame changes
+  --rel-addr              Under each row, show the write's target as base+0xNN
+  --decode                Under each row, show len, num, and guard decoded from its little-endian bytes
+  --endian <ORDER>        Decode field values as little (default) or big-endian; the dump is unchanged
+  --bits                  Under each row, show the bit-level change of every changed byte
+  --ascii                 Add an xxd-style ASCII column to the right of each row
+  --offsets               Start each row with the offset of its first byte (0x0000)
+  --bytes-per-row <N>     Wrap the hex dump every N bytes (default: one line per snapshot)
+  --view <UNIT>           Draw the dump as bytes (default) or as little-endian u32 words
+  --field-colors          Tint every byte by the field that owns it (layout map view)
+  --quiz                  Predict whether each test panics before it runs, and keep score
+  --pause-on-invalid      Stop with a banner at the first write that makes len > BUFFER_SIZE
+  --verify-invariants     Check that a fresh Frame matches the documented invariants before running
+  --check-invariant       Print Frame::check_invariant() before and after each test's writes
+  --no-volatile           Read the After: line with plain loads instead of read_volatile
+  --histogram             After the summary, chart how many tests corrupted each byte offset
+  --sizes                 Compare size, alignment, and padding of the victim structs, then exit
+  --explain-unsafecell    Explain (and show) why the fields need UnsafeCell, then exit
+  --explain-elision       Explain why a len-free sum needs no bounds checks, then exit
+  --explain-inline        Explain why #[inline(always)] doesn't change what volatile means, then exit
+  --tee <PATH>            Also write the human-readable output to PATH
+  --tee-strip-color       Leave ANSI colors out of the --tee file
+  --color <WHEN>          Use ANSI colors: auto (default; honors NO_COLOR and
+                          CLICOLOR_FORCE), always, or never
+  --palette <NAME>        default (red 91 / green 92), colorblind (orange 33 / blue 94,
+                          [changed] vs <watched>), or monochrome (markers only)
+  -V, --version           Print the version
+  -h, --help              Print this help";
+
+/// What the default demo writes to stdout (`--format`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// The human-readable hex dump
+    Text,
+    /// One JSON object per test, written when the test finishes
+    Json,
+    /// The colored dump as an HTML document (or `--html-fragment`), written after the run
+    Html,
+    /// The last test's final bytes drawn as an SVG grid, written after the run
+    Svg,
+    /// A header, then one row of byte values per write, written as each test finishes
+    Csv,
+}
+
+/// Which end of the range each test's overflow starts from (`--direction`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    /// `buffer[0]` first, marching up into `len`, `num`, and `guard`
+    #[default]
+    Forward,
+    /// The last byte of the range first, working back down to `buffer[0]`
+    Reverse,
+}
+
+/// Where each test's `Frame` lives (`--location`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Location {
+    /// A local variable in the demo loop
+    #[default]
+    Stack,
+    /// A `Box<SizedFrame<B>>`, written through the boxed pointer
+    Heap,
+}
+
+/// Image format used by `--frames`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameFormat {
+    /// Binary PPM (P6): dependency-free and understood by most image tools
+    Ppm,
+    /// PNG, only available when built with the `png` feature
+    Png,
+}
+
+/// Write lengths the demo runs when `--writes` isn't given: one that fits,
+/// then ever further past the end of the buffer.
+pub const DEFAULT_WRITES: &[usize] = &[5, 6, 8, 10, 12];
+
+/// Everything the user asked for on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// Run this scenario instead of the default demo (`--scenario`)
+    pub scenario: Option<String>,
+
+    /// Print the available scenarios and exit (`--list-scenarios`)
+    pub list_scenarios: bool,
+
+    /// Directory to write rasterized frames into (`--frames`)
+    pub frames: Option<PathBuf>,
+
+    /// Image format for the rasterized frames (`--frame-format`)
+    pub frame_format: FrameFormat,
+
+    /// File to export every printed snapshot to, as NDJSON (`--history-export`)
+    pub history_export: Option<PathBuf>,
+
+    /// Self-contained animated HTML page to write after the run (`--html-anim`)
+    pub html_anim: Option<PathBuf>,
+
+    /// File to write a TikZ figure of the last test's corruption to (`--tikz`)
+    pub tikz: Option<PathBuf>,
+
+    /// Environment variable to read the initial `len` from (`--len-from-env`)
+    pub len_from_env: Option<String>,
+
+    /// Raw Frame bytes to load instead of running the write loop (`--load`)
+    pub load: Option<PathBuf>,
+
+    /// File to write the last test's final Frame bytes to (`--dump`)
+    pub dump: Option<PathBuf>,
+
+    /// File to record every test's writes and snapshots to (`--save-trace`)
+    pub save_trace: Option<PathBuf>,
+
+    /// Recorded run to play back instead of writing past the buffer (`--replay`)
+    pub replay: Option<PathBuf>,
+
+    /// Run the safe-Rust model of the demo instead of the real thing (`--simulate`, `--miri-safe`)
+    pub simulate: bool,
+
+    /// Write lengths to sweep instead of running the default demo (`--sweep`)
+    pub sweep: Option<RangeInclusive<usize>>,
+
+    /// Write length of each test, in order (`--writes`)
+    pub writes: Vec<usize>,
+
+    /// Order of each test's writes (`--direction`)
+    pub direction: Direction,
+
+    /// Stack or heap for the frame (`--location`)
+    pub location: Location,
+
+    /// What the demo loop writes past the buffer (`--fill`, `--fill-value`, `--random-fill`)
+    pub fill: FillPattern,
+
+    /// Aim the overflow at `num`, leaving `len` and `guard` as they were (`--target-num`)
+    pub target_num: Option<i32>,
+
+    /// Size of `Frame`'s buffer in the default demo (`--buffer-size`)
+    pub buffer_size: usize,
+
+    /// Which safe function runs against the corrupted frame (`--consumer`)
+    pub consumer: Consumer,
+
+    /// Trace each read made by the safe consumer (`--highlight-reads`)
+    pub highlight_reads: bool,
+
+    /// Don't catch `safe_sum_prefix`'s panic; let it end the process (`--no-catch`)
+    pub no_catch: bool,
+
+    /// Pause after each printed row (`--delay`)
+    pub delay: Option<Duration>,
+
+    /// Apply `--delay` only to writes that hit a watched field (`--dramatic`)
+    pub dramatic: bool,
+
+    /// Wait for Enter after each write when stdin is a terminal (`--step`)
+    pub step: bool,
+
+    /// Only print rows where a byte changed; first and last always print (`--changes-only`)
+    pub changes_only: bool,
+
+    /// Stream events as JSON Lines instead of printing the dump (`--jsonl`)
+    pub jsonl: bool,
+
+    /// One verdict line per test instead of the dump (`--quiet`)
+    pub quiet: bool,
+
+    /// Text dump, one JSON object per test, an HTML page, or an SVG grid (`--format`)
+    pub format: OutputFormat,
+
+    /// Print only the `<pre>` for `--format html` (`--html-fragment`)
+    pub html_fragment: bool,
+
+    /// Randomize `buffer` and `num` in each fresh Frame (`--random-init`)
+    pub random_init: bool,
+
+    /// Also randomize the guard sentinel (`--random-guard`, needs `--random-init`)
+    pub random_guard: bool,
+
+    /// The guard's starting value, and what corruption is judged against (`--guard`)
+    pub guard: u32,
+
+    /// Seed for the PRNG (`--seed`); picked from the clock when absent
+    pub seed: Option<u64>,
+
+    /// Watchpoint on this Frame offset (`--break-on-offset`)
+    pub break_on_offset: Option<usize>,
+
+    /// Show each write's target as an offset from the struct base (`--rel-addr`)
+    pub rel_addr: bool,
+
+    /// Decode the watched fields from each printed row's bytes (`--decode`)
+    pub decode: bool,
+
+    /// Byte order for decoded and printed field values (`--endian`)
+    pub endian: Endian,
+
+    /// Annotate changed bytes with their bit patterns (`--bits`)
+    pub bits: bool,
+
+    /// Show the printable-ASCII column next to the hex (`--ascii`)
+    pub ascii: bool,
+
+    /// Prefix each row with its starting byte offset (`--offsets`)
+    pub offsets: bool,
+
+    /// Wrap each dump row after this many bytes (`--bytes-per-row`)
+    pub bytes_per_row: usize,
+
+    /// Draw the dump as bytes or as `u32` words (`--view`)
+    pub view: ViewMode,
+
+    /// Color bytes by owning field instead of by change state (`--field-colors`)
+    pub field_colors: bool,
+
+    /// Ask for a prediction before each test and keep score (`--quiz`)
+    pub quiz: bool,
+
+    /// Announce (and on a terminal, pause at) the first write that breaks `len` (`--pause-on-invalid`)
+    pub pause_on_invalid: bool,
+
+    /// Self-check the documented invariants before any corruption (`--verify-invariants`)
+    pub verify_invariants: bool,
+
+    /// Print `check_invariant()` around each test's writes (`--check-invariant`)
+    pub check_invariant: bool,
+
+    /// Read the `After:` fields with plain loads, not `read_volatile` (`--no-volatile`)
+    pub no_volatile: bool,
+
+    /// Chart corrupted tests per byte offset at the end of the run (`--histogram`)
+    pub histogram: bool,
+
+    /// Print the victim-struct size comparison and exit (`--sizes`)
+    pub sizes: bool,
+
+    /// Print the UnsafeCell explanation and exit (`--explain-unsafecell`)
+    pub explain_unsafecell: bool,
+
+    /// Print the bounds-check elision explanation and exit (`--explain-elision`)
+    pub explain_elision: bool,
+
+    /// Print the inlining-vs-volatile explanation and exit (`--explain-inline`)
+    pub explain_inline: bool,
+
+    /// Transcript file that receives a copy of the human-readable output (`--tee`)
+    pub tee: Option<PathBuf>,
+
+    /// Strip ANSI colors from the `--tee` copy (`--tee-strip-color`)
+    pub tee_strip_color: bool,
+
+    /// Force ANSI colors on or off (`--color always|never`); `None` auto-detects
+    pub color: Option<bool>,
+
+    /// Styles for changed, watched, corrupted, and padding bytes (`--palette`)
+    pub palette: ColorScheme,
+
+    /// Print the version and exit (`--version`)
+    pub version: bool,
+
+    /// Print usage and exit (`--help`)
+    pub help: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            scenario: None,
+            list_scenarios: false,
+            frames: None,
+            frame_format: FrameFormat::Ppm,
+            history_export: None,
+            html_anim: None,
+            tikz: None,
+            len_from_env: None,
+            load: None,
+            dump: None,
+            save_trace: None,
+            replay: None,
+            simulate: false,
+            sweep: None,
+            writes: DEFAULT_WRITES.to_vec(),
+            direction: Direction::Forward,
+            location: Location::Stack,
+            fill: FillPattern::Index,
+            target_num: None,
+            buffer_size: BUFFER_SIZE,
+            consumer: Consumer::Sum,
+            highlight_reads: false,
+            no_catch: false,
+            delay: None,
+            dramatic: false,
+            step: false,
+            changes_only: false,
+            jsonl: false,
+            quiet: false,
+            format: OutputFormat::Text,
+            html_fragment: false,
+            random_init: false,
+            random_guard: false,
+            guard: GUARD_SENTINEL,
+            seed: None,
+            break_on_offset: None,
+            rel_addr: false,
+            decode: false,
+            endian: Endian::Little,
+            bits: false,
+            ascii: false,
+            offsets: false,
+            bytes_per_row: usize::MAX,
+            view: ViewMode::Bytes,
+            field_colors: false,
+            quiz: false,
+            pause_on_invalid: false,
+            verify_invariants: false,
+            check_invariant: false,
+            no_volatile: false,
+            histogram: false,
+            sizes: false,
+            explain_unsafecell: false,
+            explain_elision: false,
+            explain_inline: false,
+            tee: None,
+            tee_strip_color: false,
+            color: None,
+            palette: ColorScheme::DEFAULT,
+            version: false,
+            help: false,
+        }
+    }
+}
+
+impl Options {
+    /// Parse options from an argument iterator (without the program name).
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut opts = Options::default();
+        let mut args = args.into_iter();
+        // The first of --fill, --fill-value, and --random-fill given, to reject a second
+        let mut fill_flag: Option<String> = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--scenario" => opts.scenario = Some(value(&mut args, &arg)?),
+                "--list-scenarios" => opts.list_scenarios = true,
+                "--frames" => opts.frames = Some(PathBuf::from(value(&mut args, &arg)?)),
+                "--frame-format" => {
+                    opts.frame_format = match value(&mut args, &arg)?.as_str() {
+                        "ppm" => FrameFormat::Ppm,
+                        "png" if cfg!(feature = "png") => FrameFormat::Png,
+                        "png" => {
+                            return Err("png frames require building with `--features png`".into())
+                        }
+                        other => return Err(format!("unknown frame format `{other}` (expected ppm or png)")),
+                    }
+                }
+                "--history-export" => opts.history_export = Some(PathBuf::from(value(&mut args, &arg)?)),
+                "--html-anim" => opts.html_anim = Some(PathBuf::from(value(&mut args, &arg)?)),
+                "--tikz" => opts.tikz = Some(PathBuf::from(value(&mut args, &arg)?)),
+                "--len-from-env" => opts.len_from_env = Some(value(&mut args, &arg)?),
+                "--load" => opts.load = Some(PathBuf::from(value(&mut args, &arg)?)),
+                "--dump" => opts.dump = Some(PathBuf::from(value(&mut args, &arg)?)),
+                "--save-trace" => opts.save_trace = Some(PathBuf::from(value(&mut args, &arg)?)),
+                "--replay" => opts.replay = Some(PathBuf::from(value(&mut args, &arg)?)),
+                "--simulate" | "--miri-safe" => opts.simulate = true,
+                "--sweep" => opts.sweep = Some(parse_range(&value(&mut args, &arg)?)?),
+                "--writes" => opts.writes = parse_length_list(&value(&mut args, &arg)?)?,
+                "--location" => {
+                    opts.location = match value(&mut args, &arg)?.as_str() {
+                        "stack" => Location::Stack,
+                        "heap" => Location::Heap,
+                        other => return Err(format!("unknown location `{other}` (expected stack or heap)")),
+                    }
+                }
+                "--direction" => {
+                    opts.direction = match value(&mut args, &arg)?.as_str() {
+                        "forward" => Direction::Forward,
+                        "reverse" => Direction::Reverse,
+                        other => return Err(format!("unknown direction `{other}` (expected forward or reverse)")),
+                    }
+                }
+                "--fill" | "--fill-value" | "--random-fill" => {
+                    if let Some(earlier) = fill_flag.replace(arg.clone()) {
+                        return Err(format!("`{earlier}` and `{arg}` both pick the overflow bytes; use one"));
+                    }
+                    opts.fill = match arg.as_str() {
+                        "--fill" => parse_fill(&value(&mut args, &arg)?)?,
+                        "--fill-value" => FillPattern::Bytes(parse_byte_list(&value(&mut args, &arg)?)?),
+                        _ => FillPattern::Random,
+                    };
+                }
+                "--target-num" => opts.target_num = Some(parse_i32(&value(&mut args, &arg)?)?),
+                "--buffer-size" => {
+                    let size = parse_u64(&value(&mut args, &arg)?)? as usize;
+                    if !(1..=MAX_BUFFER_SIZE).contains(&size) {
+                        return Err(format!("`--buffer-size` must be between 1 and {MAX_BUFFER_SIZE}, got {size}"));
+                    }
+                    opts.buffer_size = size;
+                }
+                "--consumer" => {
+                    opts.consumer = match value(&mut args, &arg)?.as_str() {
+                        "sum" => Consumer::Sum,
+                        "max" => Consumer::Max,
+                        "copy" => Consumer::Copy,
+                        other => return Err(format!("unknown consumer `{other}` (expected sum, max, or copy)")),
+                    }
+                }
+                "--highlight-reads" => opts.highlight_reads = true,
+                "--no-catch" => opts.no_catch = true,
+                "--delay" => opts.delay = Some(Duration::from_millis(parse_u64(&value(&mut args, &arg)?)?)),
+                "--dramatic" => opts.dramatic = true,
+                "--step" => opts.step = true,
+                "--changes-only" => opts.changes_only = true,
+                "--jsonl" => opts.jsonl = true,
+                "--quiet" => opts.quiet = true,
+                "--format" => {
+                    opts.format = match value(&mut args, &arg)?.as_str() {
+                        "text" => OutputFormat::Text,
+                        "json" => OutputFormat::Json,
+                        "html" => OutputFormat::Html,
+                        "svg" => OutputFormat::Svg,
+                        "csv" => OutputFormat::Csv,
+                        other => {
+                            let expected = "text, json, html, svg, or csv";
+                            return Err(format!("unknown output format `{other}` (expected {expected})"));
+                        }
+                    }
+                }
+                "--html-fragment" => opts.html_fragment = true,
+                "--random-init" => opts.random_init = true,
+                "--random-guard" => opts.random_guard = true,
+                "--guard" => {
+                    let raw = value(&mut args, &arg)?;
+                    let guard = u32::try_from(parse_u64(&raw)?);
+                    opts.guard = guard.map_err(|_| format!("`{raw}` does not fit in 32 bits"))?;
+                }
+                "--seed" => opts.seed = Some(parse_u64(&value(&mut args, &arg)?)?),
+                "--break-on-offset" => opts.break_on_offset = Some(parse_u64(&value(&mut args, &arg)?)? as usize),
+                "--rel-addr" => opts.rel_addr = true,
+                "--decode" => opts.decode = true,
+                "--endian" => {
+                    opts.endian = match value(&mut args, &arg)?.as_str() {
+                        "little" => Endian::Little,
+                        "big" => Endian::Big,
+                        other => return Err(format!("unknown byte order `{other}` (expected little or big)")),
+                    }
+                }
+                "--view" => {
+                    opts.view = match value(&mut args, &arg)?.as_str() {
+                        "bytes" => ViewMode::Bytes,
+                        "words" => ViewMode::Words,
+                        other => return Err(format!("unknown view `{other}` (expected bytes or words)")),
+                    }
+                }
+                "--bits" => opts.bits = true,
+                "--ascii" => opts.ascii = true,
+                "--offsets" => opts.offsets = true,
+                "--bytes-per-row" => {
+                    opts.bytes_per_row = parse_u64(&value(&mut args, &arg)?)? as usize;
+                    if opts.bytes_per_row == 0 {
+                        return Err("`--bytes-per-row` must be at least 1".into());
+                    }
+                }
+                "--field-colors" => opts.field_colors = true,
+                "--quiz" => opts.quiz = true,
+                "--pause-on-invalid" => opts.pause_on_invalid = true,
+                "--verify-invariants" => opts.verify_invariants = true,
+                "--check-invariant" => opts.check_invariant = true,
+                "--no-volatile" => opts.no_volatile = true,
+                "--histogram" => opts.histogram = true,
+                "--sizes" => opts.sizes = true,
+                "--explain-unsafecell" => opts.explain_unsafecell = true,
+                "--explain-elision" => opts.explain_elision = true,
+                "--explain-inline" => opts.explain_inline = true,
+                "--tee" => opts.tee = Some(PathBuf::from(value(&mut args, &arg)?)),
+                "--tee-strip-color" => opts.tee_strip_color = true,
+                "--color" => {
+                    opts.color = match value(&mut args, &arg)?.as_str() {
+                        "auto" => None,
+                        "always" => Some(true),
+                        "never" => Some(false),
+                        other => return Err(format!("unknown color mode `{other}` (expected auto, always, or never)")),
+                    }
+                }
+                "--palette" => {
+                    opts.palette = match value(&mut args, &arg)?.as_str() {
+                        "default" => ColorScheme::DEFAULT,
+                        "colorblind" => ColorScheme::colorblind(),
+                        "monochrome" => ColorScheme::monochrome(),
+                        other => {
+                            return Err(format!("unknown palette `{other}` (expected default, colorblind, or monochrome)"))
+                        }
+                    }
+                }
+                "-V" | "--version" => opts.version = true,
+                "-h" | "--help" => opts.help = true,
+                other => return Err(format!("unknown argument `{other}`")),
+            }
+        }
+
+        if opts.random_guard && !opts.random_init {
+            return Err("`--random-guard` only makes sense with `--random-init`".into());
+        }
+        if opts.guard != GUARD_SENTINEL {
+            if opts.random_guard {
+                return Err("`--guard` and `--random-guard` both pick the guard; use one".into());
+            }
+            if opts.simulate || opts.sweep.is_some() || opts.scenario.is_some() {
+                return Err("`--guard` only applies to the default demo and `--load`".into());
+            }
+        }
+
+        if opts.fill == FillPattern::Random
+            && (opts.simulate || opts.sweep.is_some() || opts.scenario.is_some() || opts.load.is_some())
+        {
+            return Err("`--fill random` only applies to the default demo".into());
+        }
+
+        if opts.tee_strip_color && opts.tee.is_none() {
+            return Err("`--tee-strip-color` only makes sense with `--tee`".into());
+        }
+
+        if opts.dramatic && opts.delay.is_none() {
+            return Err("`--dramatic` only makes sense with `--delay`".into());
+        }
+
+        if opts.consumer != Consumer::Sum {
+            if opts.simulate || opts.sweep.is_some() || opts.scenario.is_some() {
+                return Err("`--consumer` only applies to the default demo and `--load`".into());
+            }
+            if opts.highlight_reads || opts.jsonl {
+                return Err("`--highlight-reads` and `--jsonl` trace safe_sum_prefix; drop `--consumer`".into());
+            }
+        }
+
+        if opts.no_catch && (opts.simulate || opts.sweep.is_some() || opts.scenario.is_some()) {
+            return Err("`--no-catch` only applies to the default demo and `--load`".into());
+        }
+
+        if opts.decode && (opts.simulate || opts.sweep.is_some() || opts.scenario.is_some()) {
+            return Err("`--decode` only applies to the default demo and `--load`".into());
+        }
+        if opts.endian != Endian::Little && (opts.simulate || opts.sweep.is_some() || opts.scenario.is_some()) {
+            return Err("`--endian` only applies to the default demo and `--load`".into());
+        }
+        if opts.view != ViewMode::Bytes && (opts.sweep.is_some() || opts.scenario.is_some()) {
+            return Err("`--view` only applies to the default demo, `--load`, and `--simulate`".into());
+        }
+
+        if opts.step && (opts.simulate || opts.sweep.is_some() || opts.scenario.is_some() || opts.load.is_some()) {
+            return Err("`--step` only applies to the default demo".into());
+        }
+        if opts.direction != Direction::Forward
+            && (opts.simulate || opts.sweep.is_some() || opts.scenario.is_some() || opts.load.is_some())
+        {
+            return Err("`--direction` only applies to the default demo".into());
+        }
+        if opts.location != Location::Stack
+            && (opts.simulate || opts.sweep.is_some() || opts.scenario.is_some() || opts.load.is_some())
+        {
+            return Err("`--location` only applies to the default demo".into());
+        }
+        if opts.changes_only && (opts.simulate || opts.sweep.is_some() || opts.scenario.is_some() || opts.load.is_some())
+        {
+            return Err("`--changes-only` only applies to the default demo".into());
+        }
+        if opts.check_invariant
+            && (opts.simulate || opts.sweep.is_some() || opts.scenario.is_some() || opts.load.is_some())
+        {
+            return Err("`--check-invariant` only applies to the default demo".into());
+        }
+        if opts.no_volatile && (opts.simulate || opts.sweep.is_some() || opts.scenario.is_some() || opts.load.is_some())
+        {
+            return Err("`--no-volatile` only applies to the default demo".into());
+        }
+        if opts.histogram && (opts.simulate || opts.sweep.is_some() || opts.scenario.is_some() || opts.load.is_some()) {
+            return Err("`--histogram` only applies to the default demo".into());
+        }
+
+        if opts.buffer_size != BUFFER_SIZE && (opts.simulate || opts.sweep.is_some() || opts.scenario.is_some()) {
+            return Err("`--buffer-size` only applies to the default demo".into());
+        }
+
+        if opts.writes != DEFAULT_WRITES && (opts.sweep.is_some() || opts.scenario.is_some()) {
+            return Err("`--writes` only applies to the default demo and `--simulate`".into());
+        }
+
+        if opts.target_num.is_some() {
+            if opts.simulate || opts.sweep.is_some() || opts.scenario.is_some() || opts.load.is_some() {
+                return Err("`--target-num` only applies to the default demo".into());
+            }
+            if opts.writes != DEFAULT_WRITES {
+                return Err("`--target-num` picks its own write length; drop `--writes`".into());
+            }
+        }
+
+        if opts.simulate || opts.sweep.is_some() || opts.scenario.is_some() {
+            if opts.load.is_some() {
+                return Err("`--load` replaces the default demo's writes; it can't be combined with other modes".into());
+            }
+            if opts.dump.is_some() {
+                return Err("`--dump` only applies to the default demo and `--load`".into());
+            }
+        }
+
+        if opts.save_trace.is_some() || opts.replay.is_some() {
+            let flag = if opts.replay.is_some() { "--replay" } else { "--save-trace" };
+            if opts.simulate || opts.sweep.is_some() || opts.scenario.is_some() || opts.load.is_some() {
+                return Err(format!("`{flag}` only applies to the default demo"));
+            }
+            // Banners the trace doesn't record, so the replay couldn't repeat them
+            if opts.target_num.is_some() || opts.len_from_env.is_some() {
+                return Err(format!("`{flag}` can't be combined with `--target-num` or `--len-from-env`"));
+            }
+            // The heap banner prints an address, which no replay would land on again
+            if opts.location == Location::Heap {
+                return Err(format!("`{flag}` can't be combined with `--location heap`"));
+            }
+        }
+
+        if opts.replay.is_some() {
+            // The trace already decided the buffer and every byte written
+            let choices = [
+                ("--save-trace", opts.save_trace.is_some()),
+                ("--writes", opts.writes != DEFAULT_WRITES),
+                ("--buffer-size", opts.buffer_size != BUFFER_SIZE),
+                ("--fill", opts.fill != FillPattern::Index),
+                ("--random-init", opts.random_init),
+                ("--guard", opts.guard != GUARD_SENTINEL),
+                ("--direction", opts.direction != Direction::Forward),
+            ];
+            if let Some((flag, _)) = choices.iter().find(|(_, set)| *set) {
+                return Err(format!("`--replay` plays back what was recorded; drop `{flag}`"));
+            }
+        }
+
+        if opts.load.is_some() && opts.stdout_is_data() {
+            return Err("`--load` only prints the text dump".into());
+        }
+
+        if opts.format != OutputFormat::Text {
+            let name = match opts.format {
+                OutputFormat::Json => "json",
+                OutputFormat::Html => "html",
+                OutputFormat::Csv => "csv",
+                _ => "svg",
+            };
+            if opts.jsonl {
+                return Err(format!("`--format {name}` and `--jsonl` can't share stdout"));
+            }
+            if opts.simulate || opts.sweep.is_some() || opts.scenario.is_some() {
+                return Err(format!("`--format {name}` only applies to the default demo"));
+            }
+        }
+
+        if opts.quiet {
+            if opts.simulate || opts.sweep.is_some() || opts.scenario.is_some() || opts.load.is_some() {
+                return Err("`--quiet` only applies to the default demo".into());
+            }
+            if opts.jsonl || opts.format != OutputFormat::Text {
+                return Err("`--quiet` has its own stdout format; drop `--jsonl`/`--format`".into());
+            }
+            if opts.quiz || opts.step {
+                return Err("`--quiet` can't ask questions; drop `--quiz`/`--step`".into());
+            }
+        }
+
+        if opts.html_fragment && opts.format != OutputFormat::Html {
+            return Err("`--html-fragment` only makes sense with `--format html`".into());
+        }
+
+        Ok(opts)
+    }
+
+    /// Is stdout reserved for machine-readable output (`--jsonl`, `--format json|html|svg|csv`,
+    /// `--quiet`)?
+    ///
+    /// The human-readable text is kept off stdout in that case so the data stays parseable.
+    pub fn stdout_is_data(&self) -> bool {
+        self.jsonl || self.quiet || self.format != OutputFormat::Text
+    }
+}
+
+/// Pull the value for a flag that requires one.
+fn value(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<String, String> {
+    args.next().ok_or_else(|| format!("`{flag}` requires a value"))
+}
+
+/// Parse a number given either in decimal or as `0x` hex.
+fn parse_u64(raw: &str) -> Result<u64, String> {
+    let parsed = match raw.strip_prefix("0x").or_else(|| raw.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(&hex.replace('_', ""), 16),
+        None => raw.replace('_', "").parse(),
+    };
+    parsed.map_err(|err| format!("`{raw}` is not a valid number: {err}"))
+}
+
+/// Parse an `i32`: decimal (possibly negative), or `0x` hex for the raw bit pattern.
+fn parse_i32(raw: &str) -> Result<i32, String> {
+    if raw.starts_with('-') {
+        return raw.replace('_', "").parse().map_err(|err| format!("`{raw}` is not a valid i32: {err}"));
+    }
+    let n = parse_u64(raw)?;
+    if raw.starts_with("0x") || raw.starts_with("0X") {
+        u32::try_from(n).map(|bits| bits as i32).map_err(|_| format!("`{raw}` does not fit in 32 bits"))
+    } else {
+        i32::try_from(n).map_err(|_| format!("`{raw}` does not fit in an i32"))
+    }
+}
+
+/// Parse a Rust-style range of lengths: `a..b` (exclusive) or `a..=b` (inclusive).
+fn parse_range(raw: &str) -> Result<RangeInclusive<usize>, String> {
+    let bad = || format!("`{raw}` is not a range (expected e.g. 0..16 or 0..=16)");
+    let (start, end, inclusive) = match raw.split_once("..=") {
+        Some((start, end)) => (start, end, true),
+        None => {
+            let (start, end) = raw.split_once("..").ok_or_else(bad)?;
+            (start, end, false)
+        }
+    };
+    let start = parse_u64(start.trim())? as usize;
+    let end = parse_u64(end.trim())? as usize;
+    let empty = || format!("`{raw}` is an empty range");
+    let end = if inclusive { end } else { end.checked_sub(1).ok_or_else(empty)? };
+    if start > end {
+        return Err(empty());
+    }
+    Ok(start..=end)
+}
+
+/// Parse a comma-separated list of write lengths, each decimal or `0x` hex.
+fn parse_length_list(raw: &str) -> Result<Vec<usize>, String> {
+    if raw.trim().is_empty() {
+        return Err("`--writes` needs at least one length (e.g. 5,6,8,20)".into());
+    }
+    if raw.split(',').any(|item| item.trim().is_empty()) {
+        return Err(format!("`{raw}` has an empty length between its commas"));
+    }
+    raw.split(',').map(|item| parse_u64(item.trim()).map(|n| n as usize)).collect()
+}
+
+/// Parse a comma-separated list of hex bytes.
+fn parse_byte_list(raw: &str) -> Result<Vec<u8>, String> {
+    raw.split(',').map(|item| parse_hex_byte(item.trim())).collect()
+}
+
+/// Parse one byte as hex, with or without `0x`: `41`, `0x41`, and `0X41` are all `A`.
+///
+/// Always hex, never decimal, so a byte copied out of a dump means the same thing here.
+fn parse_hex_byte(raw: &str) -> Result<u8, String> {
+    let digits = raw.strip_prefix("0x").or_else(|| raw.strip_prefix("0X")).unwrap_or(raw);
+    if digits.is_empty() || digits.len() > 2 {
+        return Err(format!("`{raw}` is not a hex byte (expected 00 to ff, optionally with 0x)"));
+    }
+    u8::from_str_radix(digits, 16).map_err(|err| format!("`{raw}` is not a hex byte: {err}"))
+}
+
+/// Parse a `--fill` pattern: `index`, `const:NN` (a hex byte), `ascii`, or `random`.
+fn parse_fill(raw: &str) -> Result<FillPattern, String> {
+    match raw {
+        "index" => Ok(FillPattern::Index),
+        "ascii" => Ok(FillPattern::Ascii),
+        "random" => Ok(FillPattern::Random),
+        _ => match raw.strip_prefix("const:") {
+            Some(byte) => parse_hex_byte(byte).map(FillPattern::Constant),
+            None => Err(format!("unknown fill pattern `{raw}` (expected index, const:NN, ascii, or random)")),
+        },
+    }
+}
+
+/// Read and parse a `u32` length from the environment variable `var`.
+pub fn read_len_env(var: &str) -> Result<u32, String> {
+    let raw = std::env::var(var).map_err(|err| match err {
+        std::env::VarError::NotPresent => format!("environment variable ${var} is not set"),
+        std::env::VarError::NotUnicode(_) => format!("environment variable ${var} is not valid UTF-8"),
+    })?;
+    raw.trim()
+        .parse::<u32>()
+        .map_err(|err| format!("${var}={raw:?} is not a valid u32 length: {err}"))
+}
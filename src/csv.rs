@@ -0,0 +1,68 @@
+//! `--format csv`: every write of every test as one spreadsheet row.
+//!
+//! The columns are `test_length,write_index`, then one per byte of the frame
+//! holding its value in hex after that write. Bytes inside a watched field
+//! are headed by the field and the byte's position in it (`len[0]`); the rest
+//! by their offset (`0x05`). Charting a column down the rows shows when that
+//! byte was hit; charting across a row shows how far the overflow had spread.
+
+use std::fmt::Write;
+
+use crate::layout::Layout;
+use crate::report::TestRun;
+
+/// The header row, newline included.
+pub fn header(layout: &Layout, watched: &[(usize, usize)]) -> String {
+    let mut row = String::from("test_length,write_index");
+    for offset in 0..layout.size {
+        let field = layout.field_index_at(offset).map(|index| &layout.fields[index]);
+        match field {
+            Some(field) if watched.iter().any(|&(start, end)| (start..end).contains(&offset)) => {
+                let _ = write!(row, ",{}[{}]", field.name, offset - field.offset);
+            }
+            _ => {
+                let _ = write!(row, ",0x{offset:02x}");
+            }
+        }
+    }
+    row.push('\n');
+    row
+}
+
+/// One row per recorded write of `run`, each ending in a newline.
+pub fn rows(run: &TestRun) -> String {
+    let mut out = String::new();
+    for step in &run.steps {
+        let _ = write!(out, "{},{}", run.write_len, step.index);
+        for byte in &step.snapshot {
+            let _ = write!(out, ",{byte:02x}");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::WriteStep;
+    use crate::{Frame, FRAME_SIZE, LEN_OFF, WATCHED};
+
+    #[test]
+    fn watched_bytes_are_headed_by_field_and_rows_hold_hex() {
+        let header = header(Frame::LAYOUT, WATCHED);
+        let columns: Vec<&str> = header.trim_end().split(',').collect();
+        assert_eq!(columns.len(), 2 + FRAME_SIZE);
+        assert_eq!(columns[..4], ["test_length", "write_index", "0x00", "0x01"]);
+        assert_eq!(columns[2 + LEN_OFF - 1], "0x07");
+        assert_eq!(columns[2 + LEN_OFF..2 + LEN_OFF + 2], ["len[0]", "len[1]"]);
+
+        let mut snapshot = vec![0u8; FRAME_SIZE];
+        snapshot[LEN_OFF] = 0xAB;
+        let mut run = TestRun::new(0, 9, Frame::LAYOUT, &[0; FRAME_SIZE]);
+        run.steps.push(WriteStep { index: 8, offset: LEN_OFF, value: 0xAB, snapshot, changed: vec![LEN_OFF] });
+        let rows = rows(&run);
+        assert!(rows.starts_with("9,8,00,"));
+        assert_eq!(rows.trim_end().split(',').nth(2 + LEN_OFF), Some("ab"));
+    }
+}
@@ -0,0 +1,848 @@
+//! The default demo: one test per write length, most of them past `buffer`.
+//!
+//! Each test gets a fresh `Frame`, writes `0, 1, 2, ...` (or `--fill-value`, or `--random-fill`)
+//! through a raw pointer one byte at a time, dumps the frame after every
+//! write, and then lets `safe_sum_prefix` trust whatever `len` ended up as.
+//! Everything optional (`--jsonl`, `--frames`, `--quiz`, ...) hangs off this
+//! loop.
+
+use std::io::{self, Write};
+use std::mem::size_of;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::panic::AssertUnwindSafe;
+
+use crate::cli::{self, Direction, Location, Options};
+use crate::output::{self, outln};
+use crate::{
+    color, csv, frames, history, html, html_anim, jsonl, layout, pause, quiz, report, rng, runner,
+    safe_sum_prefix_traced, step_prompt, svg, tikz, trace, victim, wait_for_enter, Endian, FillPattern, MemoryView,
+    SizedFrame, BUFFER_SIZE, MAX_BUFFER_SIZE,
+};
+use crate::victim::Victim;
+
+/// Run one test per `--writes` length and return their outcomes.
+pub fn run(opts: &Options) -> report::RunReport {
+    with_buffer_size!(opts.buffer_size, run_sized(opts, None))
+}
+
+/// `--replay`: run the demo again from the trace at `path`, restoring each
+/// recorded snapshot instead of writing past the buffer.
+pub fn replay(opts: &Options, path: &Path) -> Result<report::RunReport, String> {
+    let trace = trace::Trace::load(path)?;
+    Ok(with_buffer_size!(trace.buffer_size, run_sized(opts, Some(&trace))))
+}
+
+/// Size in bytes of the frame `--buffer-size <buffer_size>` runs the demo on.
+pub fn frame_size(buffer_size: usize) -> usize {
+    with_buffer_size!(buffer_size, frame_size_of())
+}
+
+fn frame_size_of<const B: usize, const N: usize>() -> usize {
+    N
+}
+
+/// Field layout of the frame `--buffer-size <buffer_size>` runs the demo on.
+pub fn layout(buffer_size: usize) -> &'static layout::Layout {
+    with_buffer_size!(buffer_size, layout_of())
+}
+
+fn layout_of<const B: usize, const N: usize>() -> &'static layout::Layout {
+    SizedFrame::<B>::LAYOUT
+}
+
+/// `run` for one buffer size `B`; `N` is the size of `SizedFrame<B>`.
+///
+/// With a `replay` trace, each test's frame comes from the recording instead.
+fn run_sized<const B: usize, const N: usize>(opts: &Options, replay: Option<&trace::Trace>) -> report::RunReport {
+    const { assert!(N == size_of::<SizedFrame<B>>()) };
+
+    let buf_off = SizedFrame::<B>::buffer_offset();
+    let len_off = SizedFrame::<B>::len_offset();
+    let frame_layout = SizedFrame::<B>::LAYOUT;
+    let padding = frame_layout.padding();
+
+    // With --jsonl, stdout carries the event stream and nothing else
+    let mut events = opts.jsonl.then(jsonl::EventStream::new);
+
+    // With --format json, stdout carries one TestRun object per test instead
+    let json_output = opts.format == cli::OutputFormat::Json;
+
+    // With --format csv, stdout carries a header and then one row per write
+    let csv_output = opts.format == cli::OutputFormat::Csv;
+    if csv_output {
+        let _ = io::stdout().lock().write_all(csv::header(frame_layout, SizedFrame::<B>::WATCHED).as_bytes());
+    }
+
+    // With --save-trace, the same TestRun objects are kept for one file at the end
+    let mut recorded: Vec<report::TestRun> = Vec::new();
+
+    // With --format html, stdout carries one HTML page of every test, written at the end
+    let mut html_dump = (opts.format == cli::OutputFormat::Html).then(html::HtmlDump::new);
+
+    // Optional NDJSON export of every printed snapshot
+    let mut history_writer = opts.history_export.as_deref().map(|path| {
+        history::HistoryWriter::create(path).unwrap_or_else(|err| {
+            eprintln!("error: cannot create history file {}: {err}", path.display());
+            std::process::exit(1);
+        })
+    });
+
+    // Optional animated HTML page, rendered once every test has run
+    let mut html_animation = opts.html_anim.is_some().then(html_anim::HtmlAnimation::new);
+
+    // Optional image output: one frame per rendered row
+    let mut frame_writer = opts.frames.as_deref().map(|dir| {
+        frames::FrameWriter::new(dir, opts.frame_format).unwrap_or_else(|err| {
+            eprintln!("error: cannot create frame directory {}: {err}", dir.display());
+            std::process::exit(1);
+        })
+    });
+
+    // ========================================================================
+    // STEP 1: Print the struct layout
+    // ========================================================================
+
+    outln!("=======================================================");
+    outln!("   UNSAFE MEMORY CORRUPTION DEMO");
+    outln!("=======================================================\n");
+
+    victim::print_layout::<SizedFrame<B>>();
+
+    // Drawn by a view set up like the ones below, so it lists exactly what they'll show
+    let mut legend_view = display_view::<B, N>(opts);
+    legend_view.sentinel = Some((SizedFrame::<B>::guard_offset(), opts.guard));
+    legend_view.print_legend(opts.highlight_reads);
+    outln!();
+    announce_endian(opts.endian);
+    if opts.no_volatile {
+        outln!("WARNING: --no-volatile reads the After: line with plain loads. What they return");
+        outln!("depends on the optimization level: at opt-level 0 they always match memory, with");
+        outln!("optimizations on they may report a value the compiler remembers instead.\n");
+    }
+
+    // ========================================================================
+    // STEP 1b: Optionally take `len` from untrusted external input
+    // ========================================================================
+
+    // With --len-from-env, every fresh Frame starts with a length that came
+    // from outside the program. No pointer tricks are needed to break safe
+    // code: if the environment says 9, `buffer[..9]` panics all by itself.
+    let external_len = opts.len_from_env.as_deref().map(|var| {
+        let len = cli::read_len_env(var).unwrap_or_else(|err| {
+            eprintln!("error: {err}");
+            std::process::exit(2);
+        });
+
+        outln!("───────────────────────────────────────────────────────");
+        outln!("UNTRUSTED INPUT: len = {len} (from ${var})");
+        outln!("───────────────────────────────────────────────────────");
+
+        let frame = SizedFrame::<B>::new();
+        frame.write_len_volatile(len);
+        let name = opts.consumer.name();
+        match run_safe_code(opts, || opts.consumer.run(&frame)) {
+            Ok(value) => outln!("{name}() = {value} before any unsafe writes (len is within bounds)"),
+            Err(_) => outln!("{name}() PANICKED before any unsafe writes! (${var} said len = {len} > {B})"),
+        }
+        outln!("The root problem is trusting an external length, not raw pointers.\n");
+
+        len
+    });
+
+    // With --random-init, every test starts from different (but reproducible) bytes;
+    // with --random-fill, the overflow writes them. Both draw from one generator,
+    // so a single seed repeats the whole run. A replay takes all of this from the
+    // trace, to say what the recorded run said; its bytes come from the trace too.
+    let (random_init, random_fill, direction) = match replay {
+        Some(trace) => (trace.random_init, trace.random_fill, trace.direction),
+        None => (opts.random_init, opts.fill == FillPattern::Random, opts.direction),
+    };
+    let seed = (random_init || random_fill).then(|| {
+        let seed = replay.map_or(opts.seed, |trace| trace.seed).unwrap_or_else(rng::seed_from_time);
+        let what = match (random_init, random_fill) {
+            (true, true) => "initial values and fill bytes",
+            (true, false) => "initial values",
+            _ => "fill bytes",
+        };
+        outln!("Random {what}, seed = {seed} (rerun with --seed {seed} to repeat)\n");
+        seed
+    });
+
+    // ========================================================================
+    // STEP 2: Run the demo with increasing write lengths
+    // ========================================================================
+
+    let mut run_report = report::RunReport::default();
+    let mut quiz = opts.quiz.then(|| quiz::Quiz::new(B));
+    let mut histogram = opts.histogram.then(|| report::OffsetHistogram::new(N));
+
+    // Pristine and final bytes of the most recent test, for --tikz
+    let mut last_test = ([0u8; N], [0u8; N]);
+
+    // The library performs the writes (runner::record_scenario) and keeps the
+    // frame after each one; the loop below draws that recording write by write
+    // and reports the library's outcome. A replay draws the trace's recording.
+    let library_run = replay.is_none().then(|| {
+        let scenario = match opts.target_num {
+            Some(target) => {
+                announce_target(target);
+                runner::Scenario::TargetNum(target)
+            }
+            None => runner::Scenario::Fill,
+        };
+        runner::record_scenario(&runner::Config {
+            buffer_size: B,
+            writes: opts.writes.clone(),
+            scenario,
+            consumer: opts.consumer,
+            fill: opts.fill.clone(),
+            seed: seed.unwrap_or(0),
+            mode: runner::Mode::Overflow,
+            random_init,
+            guard: opts.guard,
+            random_guard: opts.random_guard,
+            direction,
+            initial_len: external_len,
+            location: opts.location,
+        })
+    });
+    let recordings: Vec<&report::TestRun> = match (replay, &library_run) {
+        (Some(trace), _) => trace.tests.iter().collect(),
+        (None, tests) => tests.iter().flatten().map(|test| &test.run).collect(),
+    };
+
+    for (test, recorded_test) in recordings.into_iter().enumerate() {
+        let end = recorded_test.write_len;
+        // A fresh Frame for each test, holding the recording's pristine bytes
+        let mut frame = SizedFrame::<B>::with_guard(opts.guard);
+        let frame = &mut frame;
+        let base_ptr = frame.base_ptr();
+        unsafe { restore::<N>(base_ptr, &recorded_test.initial) };
+
+        // Set up memory view for this iteration
+        let mut view = display_view::<B, N>(opts);
+        if let Some(offset) = opts.break_on_offset {
+            view.add_watchpoint(
+                offset,
+                Box::new(move |old, new| {
+                    outln!("  ● watchpoint: byte 0x{offset:02x} changed 0x{old:02x} -> 0x{new:02x}");
+                    wait_for_enter();
+                }),
+            );
+        }
+        // The moment each invariant-bearing field takes its first stray byte
+        view.add_field_alarm(Box::new(|field, i| outln!(">>> `{field}` field corrupted at write i={i}")));
+        view.capture(base_ptr);
+        let initial = view.snapshot;
+        let initial_guard = frame.read_guard_volatile();
+        // --random-guard picks a different canary; judge against the one this frame started with
+        view.sentinel = Some((SizedFrame::<B>::guard_offset(), initial_guard));
+        if let Some(events) = &mut events {
+            events.start_test(test, end);
+        }
+        let mut test_run = (json_output || csv_output || opts.save_trace.is_some())
+            .then(|| report::TestRun::new(test, end, frame_layout, &initial));
+
+        outln!("───────────────────────────────────────────────────────");
+        match direction {
+            Direction::Reverse if end > 0 => {
+                outln!("TEST: Write {end} bytes from buffer[{}] down to buffer[0]", end - 1)
+            }
+            _ => outln!("TEST: Write {} bytes starting at buffer[0]", end),
+        }
+        outln!("      (buffer is only {} bytes!)", B);
+        outln!("───────────────────────────────────────────────────────");
+        if opts.location == Location::Heap {
+            outln!("Frame is heap-allocated: Box<SizedFrame<{B}>>, {N} bytes");
+            outln!("(its neighbours are other allocations and allocator metadata, but these writes stay inside it)");
+        }
+
+        outln!(
+            "Before: len={}, num={}, guard=0x{:08X}",
+            frame.read_len_volatile(),
+            frame.read_num_volatile(),
+            frame.read_guard_volatile()
+        );
+        print_as_endian::<B, N>(&view, opts.endian);
+        if opts.check_invariant {
+            outln!("check_invariant() before: {}", describe_invariant(frame.check_invariant_with_guard(initial_guard)));
+        }
+
+        let prediction = quiz.as_mut().and_then(|quiz| quiz.ask(end));
+
+        let mut prev = view.snapshot;
+        view.print_row("init");
+        if opts.decode {
+            print_decoded::<B, N>(&view, opts.endian);
+        }
+        emit_frame(&mut frame_writer, &view, &prev);
+
+        // Track the semantic invariant, not just "some byte changed"
+        let mut len_valid = view.read_u32(len_off) as usize <= B;
+
+        // ====================================================================
+        // The writes, one frame at a time
+        // ====================================================================
+        //
+        // The library made each unchecked write past the buffer (see
+        // runner::record_scenario); here each one's frame is put back in
+        // bounds and drawn. Once `i >= B`, watch `len`, `num`, and `guard` go.
+        //
+        // --changes-only: writes held back since the last printed row
+        let mut quiet_writes = 0;
+        // --step can stop a test before its last write
+        let mut stopped = false;
+
+        for step in 0..end {
+            let Some(recorded) = recorded_test.steps.get(step) else {
+                note_quiet_writes(&mut quiet_writes);
+                outln!("    (stopped after {} of {} writes)", step, end);
+                stopped = true;
+                break;
+            };
+            unsafe { restore::<N>(base_ptr, &recorded.snapshot) };
+            // `i` is the index written; --direction reverse visits them from the top down
+            let (i, value) = (recorded.index, recorded.value);
+
+            // Capture and display the memory state after each write
+            view.capture(base_ptr);
+            emit_frame(&mut frame_writer, &view, &prev);
+            if let Some(events) = &mut events {
+                let offset = buf_off + i;
+                let field = frame_layout.field_index_at(offset).map(|f| frame_layout.fields[f].name);
+                let changed: Vec<usize> = (0..N).filter(|&b| prev[b] != view.snapshot[b]).collect();
+                events.write(i, offset, value, field, &changed);
+            }
+            if let Some(test_run) = &mut test_run {
+                test_run.steps.push(report::WriteStep {
+                    index: i,
+                    offset: buf_off + i,
+                    value,
+                    snapshot: view.snapshot.to_vec(),
+                    changed: (0..N).filter(|&b| prev[b] != view.snapshot[b]).collect(),
+                });
+            }
+            // --changes-only: a write that moved nothing gets no row, unless it's the first or last
+            let visible = !opts.changes_only || step == 0 || step + 1 == end || view.snapshot != prev;
+            if visible {
+                note_quiet_writes(&mut quiet_writes);
+                view.print_write(&prev, i);
+                if opts.decode {
+                    print_decoded::<B, N>(&view, opts.endian);
+                }
+                if opts.rel_addr {
+                    print_write_target(frame_layout, buf_off + i);
+                }
+                note_padding_write(&padding, buf_off + i, i);
+            } else {
+                quiet_writes += 1;
+            }
+            prev = view.snapshot;
+
+            // --dramatic: fly through the buffer, linger on the control fields
+            if visible && (!opts.dramatic || view.is_watched(buf_off + i)) {
+                pause(opts.delay);
+            }
+
+            // The climax of the demo: the first write that makes `len` a lie
+            let len = view.read_u32(len_off);
+            if len_valid && len as usize > B {
+                len_valid = false;
+                if opts.pause_on_invalid {
+                    announce_invalid_len(len, B);
+                }
+            }
+
+            // --step: the presenter narrates, then presses Enter for the next write
+            if visible && opts.step && !step_prompt() {
+                outln!("    (stopped after {} of {} writes)", step + 1, end);
+                stopped = step + 1 < end;
+                break;
+            }
+        }
+
+        // ====================================================================
+        // Show the damage
+        // ====================================================================
+
+        // The whole test at a glance: the pristine frame over the corrupted one
+        outln!();
+        view.print_compare(&initial, ("before", "after"));
+        let volatile = (frame.read_len_volatile(), frame.read_num_volatile(), frame.read_guard_volatile());
+        if opts.no_volatile {
+            // Plain loads the optimizer is free to serve from an earlier read
+            let plain = (frame.read_len_plain(), frame.read_num_plain(), frame.read_guard_plain());
+            outln!("After:  len={}, num={}, guard=0x{:08X}   (plain reads)", plain.0, plain.1, plain.2);
+            if plain != volatile {
+                let (len, num, guard) = volatile;
+                outln!("  STALE: read_volatile sees len={len}, num={num}, guard=0x{guard:08X}");
+            }
+        } else {
+            outln!("After:  len={}, num={}, guard=0x{:08X}", volatile.0, volatile.1, volatile.2);
+        }
+        print_as_endian::<B, N>(&view, opts.endian);
+        outln!("{}", guard_verdict(frame.read_guard_volatile(), initial_guard));
+        if opts.check_invariant {
+            outln!("check_invariant() after:  {}", describe_invariant(frame.check_invariant_with_guard(initial_guard)));
+        }
+        if let Some(target) = opts.target_num {
+            let num = frame.read_num_volatile();
+            let verdict = if num == target { "hit" } else { "MISSED" };
+            outln!("read_num_volatile() = {num} (target {target}: {verdict})");
+        }
+
+        // ====================================================================
+        // Demonstrate safe code breaking
+        // ====================================================================
+        //
+        // safe_sum_prefix() is 100% safe Rust code.
+        // But it trusts that `len` is valid.
+        // If we corrupted `len` to be > 5, it will panic on bounds check.
+        //
+        if let Some(events) = &mut events {
+            events.canary_check(frame.read_guard_volatile(), initial_guard);
+        }
+        // The sum as a number, for the event stream (only ever traced with the default consumer)
+        let mut traced_sum = None;
+        let safe_result = if opts.highlight_reads || events.is_some() {
+            outln!("safe_sum_prefix() reads buffer[..{}]:", frame.read_len_volatile());
+            let result = run_safe_code(opts, || {
+                safe_sum_prefix_traced(frame, |i| {
+                    if let Some(events) = &mut events {
+                        events.read(i);
+                    }
+                    view.reading = Some(i);
+                    view.print_row(&format!("rd[{i}]"));
+                    pause(opts.delay);
+                })
+            });
+            view.reading = None;
+            traced_sum = result.as_ref().ok().copied();
+            result.map(|sum| sum.to_string())
+        } else {
+            run_safe_code(opts, || opts.consumer.run(frame))
+        };
+        let name = opts.consumer.name();
+        let outcome = match &safe_result {
+            Ok(value) => format!("{name}() = {value} (len was still valid)"),
+            Err(_) => format!("{name}() PANICKED! (len was corrupted to > {B})"),
+        };
+        outln!("{outcome}");
+        if let Some(events) = &mut events {
+            if let Err(payload) = &safe_result {
+                events.panic(panic_message(payload.as_ref()));
+            }
+            events.result(frame.read_len_volatile(), traced_sum);
+        }
+
+        if let Some(mut test_run) = test_run {
+            test_run.len = frame.read_len_volatile();
+            test_run.num = frame.read_num_volatile();
+            test_run.guard = frame.read_guard_volatile();
+            test_run.panicked = safe_result.is_err();
+            if json_output {
+                // Ignore errors, like the --jsonl stream: a closed pipe just ends the output
+                let _ = writeln!(io::stdout().lock(), "{}", test_run.to_json());
+            }
+            if csv_output {
+                let _ = io::stdout().lock().write_all(csv::rows(&test_run).as_bytes());
+            }
+            if opts.save_trace.is_some() {
+                recorded.push(test_run);
+            }
+        }
+
+        if let Some(quiz) = &mut quiz {
+            quiz.reveal(prediction, safe_result.is_err());
+        }
+        if opts.quiet {
+            let verdict = if safe_result.is_err() { "panicked" } else { "ok" };
+            let _ = writeln!(io::stdout().lock(), "writes={end} {verdict}");
+        }
+
+        run_report.push(match &library_run {
+            // What the library's run of this test came to, unless --step cut the drawing short of it
+            Some(tests) if !stopped => tests[test].outcome.clone(),
+            // A replay, or a stopped test: what the frame drawn above came to
+            _ => report::TestOutcome::new(
+                end,
+                frame_layout,
+                SizedFrame::<B>::WATCHED,
+                &initial,
+                &view.snapshot,
+                safe_result.is_err(),
+            )
+            .with_stats(view.corruption_stats()),
+        });
+        if let Some(histogram) = &mut histogram {
+            histogram.add(&view.corrupted);
+        }
+        last_test = (initial, view.snapshot);
+
+        if let Some(writer) = &mut history_writer {
+            if let Err(err) = writer.write_test(test, view.history()) {
+                eprintln!("error: failed to write history: {err}");
+                std::process::exit(1);
+            }
+        }
+        if let Some(animation) = &mut html_animation {
+            animation.add_test(test, end, view.history());
+        }
+        if let Some(dump) = &mut html_dump {
+            dump.add_test(end, &SizedFrame::<B>::separators(), view.history(), &outcome);
+        }
+
+        outln!();
+    }
+
+    // ========================================================================
+    // SUMMARY
+    // ========================================================================
+
+    outln!("=======================================================");
+    outln!("   KEY TAKEAWAYS");
+    outln!("=======================================================");
+    outln!();
+    outln!("1. Safe Rust code relies on invariants (len <= buffer size)");
+    outln!("2. Unsafe code can violate these invariants");
+    outln!("3. When safe code runs later, it trusts the corrupted data");
+    outln!("4. This leads to panics, crashes, or security vulnerabilities");
+    outln!();
+    outln!("This is why `unsafe` requires careful review:");
+    outln!("  - The bug is in the unsafe block");
+    outln!("  - But the crash happens in safe code!");
+    outln!("  - This makes debugging very difficult");
+
+    if let (Some(writer), Some(dir)) = (&frame_writer, &opts.frames) {
+        outln!();
+        outln!("Wrote {} frames to {}", writer.count(), dir.display());
+    }
+    if let Some(path) = &opts.history_export {
+        outln!();
+        outln!("Wrote snapshot history to {}", path.display());
+    }
+    if let (Some(animation), Some(path)) = (&html_animation, &opts.html_anim) {
+        if let Err(err) = std::fs::write(path, animation.render(frame_layout)) {
+            eprintln!("error: cannot write HTML animation to {}: {err}", path.display());
+            std::process::exit(1);
+        }
+        outln!();
+        outln!("Wrote animated HTML to {}", path.display());
+    }
+    if let Some(path) = &opts.tikz {
+        let (initial, last) = &last_test;
+        if let Err(err) = std::fs::write(path, tikz::render(frame_layout, initial, last)) {
+            eprintln!("error: cannot write TikZ figure to {}: {err}", path.display());
+            std::process::exit(1);
+        }
+        outln!();
+        outln!("Wrote TikZ figure of the last test to {}", path.display());
+    }
+    if let Some(path) = &opts.dump {
+        write_dump(path, &last_test.1);
+    }
+    if let Some(path) = &opts.save_trace {
+        let tests = recorded.len();
+        let trace = trace::Trace {
+            buffer_size: B,
+            random_init,
+            random_fill,
+            seed,
+            direction,
+            tests: recorded,
+        };
+        if let Err(err) = trace.save(path) {
+            eprintln!("error: cannot write trace to {}: {err}", path.display());
+            std::process::exit(1);
+        }
+        outln!();
+        outln!("Wrote a trace of {tests} tests to {} (play it back with --replay)", path.display());
+    }
+    if let Some(dump) = &html_dump {
+        let page = if opts.html_fragment { dump.fragment() } else { dump.document("Unsafe memory corruption demo") };
+        // Ignore errors, like --format json: a closed pipe just ends the output
+        let _ = io::stdout().lock().write_all(page.as_bytes());
+    }
+    if opts.format == cli::OutputFormat::Svg {
+        let (initial, last) = &last_test;
+        let _ = io::stdout().lock().write_all(svg::render(frame_layout, initial, last).as_bytes());
+    }
+
+    outln!();
+    run_report.print_summary_table();
+    outln!();
+    if let Some(histogram) = &histogram {
+        histogram.print(frame_layout);
+        outln!();
+    }
+    if let Some(quiz) = &quiz {
+        quiz.print_score();
+    }
+    run_report.print_report_card();
+
+    run_report
+}
+
+/// `--load`: run safe code against Frame bytes read from a file.
+///
+/// `bytes` must be exactly one frame for `--buffer-size`; the caller checks.
+pub fn run_loaded(opts: &Options, bytes: &[u8]) -> report::RunReport {
+    with_buffer_size!(opts.buffer_size, load_sized(opts, bytes))
+}
+
+/// `run_loaded` for one buffer size `B`; `N` is the size of `SizedFrame<B>`.
+fn load_sized<const B: usize, const N: usize>(opts: &Options, bytes: &[u8]) -> report::RunReport {
+    const { assert!(N == size_of::<SizedFrame<B>>()) };
+    let bytes: &[u8; N] = bytes.try_into().expect("snapshot is one frame long");
+
+    outln!("=======================================================");
+    outln!("   LOADED FRAME");
+    outln!("=======================================================\n");
+    victim::print_layout::<SizedFrame<B>>();
+    announce_endian(opts.endian);
+
+    let mut frame = SizedFrame::<B>::new();
+    let base_ptr = frame.base_ptr();
+
+    let mut view = display_view::<B, N>(opts);
+    view.capture(base_ptr);
+    let initial = view.snapshot;
+    view.print_row("init");
+    if opts.decode {
+        print_decoded::<B, N>(&view, opts.endian);
+    }
+
+    // The whole frame at once, padding included, exactly as the file has it
+    unsafe { restore::<N>(base_ptr, bytes) };
+    view.capture(base_ptr);
+    view.print_diff(&initial, "loaded");
+    if opts.decode {
+        print_decoded::<B, N>(&view, opts.endian);
+    }
+
+    outln!(
+        "Loaded: len={}, num={}, guard=0x{:08X}",
+        frame.read_len_volatile(),
+        frame.read_num_volatile(),
+        frame.read_guard_volatile()
+    );
+    print_as_endian::<B, N>(&view, opts.endian);
+    outln!("{}", guard_verdict(frame.read_guard_volatile(), opts.guard));
+    let name = opts.consumer.name();
+    let result = run_safe_code(opts, || opts.consumer.run(&frame));
+    match &result {
+        Ok(value) => outln!("{name}() = {value} (len is valid)"),
+        Err(_) => outln!("{name}() PANICKED! (len is > {B})"),
+    }
+
+    if let Some(path) = &opts.dump {
+        write_dump(path, &view.snapshot);
+    }
+
+    let mut run_report = report::RunReport::default();
+    run_report.push(report::TestOutcome::new(
+        0,
+        SizedFrame::<B>::LAYOUT,
+        SizedFrame::<B>::WATCHED,
+        &initial,
+        &view.snapshot,
+        result.is_err(),
+    )
+    .with_stats(view.corruption_stats()));
+    outln!();
+    run_report.print_report_card();
+    run_report
+}
+
+/// Copy a whole recorded frame over the one at `base_ptr` (`--load`, `--replay`).
+///
+/// Every byte lands inside the frame: this replaces the overflow, it doesn't repeat it.
+///
+/// # Safety
+///
+/// `base_ptr` must point to a live `N`-byte frame that nothing else is borrowing.
+unsafe fn restore<const N: usize>(base_ptr: *mut u8, bytes: &[u8]) {
+    let bytes: &[u8; N] = bytes.try_into().expect("a recorded frame is one frame long");
+    unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), base_ptr, N) };
+}
+
+/// `--dump`: write a frame's raw bytes where `--load` can read them back.
+fn write_dump(path: &Path, bytes: &[u8]) {
+    if let Err(err) = std::fs::write(path, bytes) {
+        eprintln!("error: cannot write Frame bytes to {}: {err}", path.display());
+        std::process::exit(1);
+    }
+    outln!();
+    outln!("Wrote the {}-byte Frame to {}", bytes.len(), path.display());
+}
+
+/// `--sweep`: run every write length in `range` on a fresh frame and tabulate the outcomes.
+///
+/// No per-write dump here; the point is the thresholds, not the animation.
+pub fn sweep(range: RangeInclusive<usize>, fill: &FillPattern) -> report::RunReport {
+    outln!("Sweeping write lengths {}..={} (buffer holds {BUFFER_SIZE}):\n", range.start(), range.end());
+
+    let config = runner::Config { writes: range.collect(), fill: fill.clone(), ..runner::Config::default() };
+    let run_report = report::RunReport { outcomes: runner::run_scenario(&config) };
+
+    run_report.print_sweep_table();
+    outln!();
+    run_report.print_report_card();
+    run_report
+}
+
+/// Print where a write landed as an offset from the struct base (`--rel-addr`).
+///
+/// Unlike a `{:p}` address this is the same on every run, and it's the number
+/// the layout table uses, so `base+0x08` can be looked up directly.
+fn print_write_target(layout: &layout::Layout, offset: usize) {
+    let owner = match layout.field_index_at(offset) {
+        Some(index) => layout.fields[index].name,
+        None => "padding",
+    };
+    outln!("       wrote base+0x{offset:02x} ({owner})");
+}
+
+/// If the write at `offset` landed in a padding gap, say so: the byte changed,
+/// but no field will ever read it.
+fn note_padding_write(padding: &[(usize, usize)], offset: usize, index: usize) {
+    if let Some(&(start, end)) = padding.iter().find(|&&(start, end)| (start..end).contains(&offset)) {
+        outln!("       i={index} wrote into 1 byte of padding [{start}..{end}) (no field affected)");
+    }
+}
+
+/// Banner for `--target-num`: what the attacker is about to write, and where.
+fn announce_target(target: i32) {
+    let bytes: Vec<String> = target.to_le_bytes().iter().map(|b| format!("{b:02x}")).collect();
+    outln!("───────────────────────────────────────────────────────");
+    outln!("TARGETED OVERFLOW: num = {target} (bytes {} in memory)", bytes.join(" "));
+    outln!("      len's own bytes are written back, so len stays valid");
+    outln!("───────────────────────────────────────────────────────\n");
+}
+
+/// `len`, `num`, and `guard` decoded from the view's snapshot in `endian` order.
+fn decode_fields<const B: usize, const N: usize>(view: &MemoryView<N>, endian: Endian) -> (u32, i32, u32) {
+    (
+        endian.read_u32(&view.snapshot, SizedFrame::<B>::len_offset()),
+        endian.read_u32(&view.snapshot, SizedFrame::<B>::num_offset()) as i32,
+        endian.read_u32(&view.snapshot, SizedFrame::<B>::guard_offset()),
+    )
+}
+
+/// The watched fields as the row above shows them (`--decode`).
+///
+/// Decoded from the snapshot's bytes, not read from the live struct, so
+/// `(05)(00)(00)(00)` always sits over `len=5`. With `--endian big` the
+/// line says so, since the same bytes then read as `len=83886080`.
+fn print_decoded<const B: usize, const N: usize>(view: &MemoryView<N>, endian: Endian) {
+    let (len, num, guard) = decode_fields::<B, N>(view, endian);
+    let row = format!("len={len} num={num} guard=0x{guard:08X}");
+    if endian == Endian::HOST {
+        outln!("       {row}");
+    } else {
+        outln!("       {row}  ({})", endian.name());
+    }
+}
+
+/// A view of a `SizedFrame<B>` with every display option from `opts` applied.
+fn display_view<const B: usize, const N: usize>(opts: &Options) -> MemoryView<N> {
+    let mut view: MemoryView<N> = MemoryView::for_victim::<SizedFrame<B>>();
+    view.apply_display_options(opts);
+    if opts.field_colors {
+        view.field_layout = Some(SizedFrame::<B>::LAYOUT);
+    }
+    view
+}
+
+/// Under a `Before:`/`After:` line, the same fields as a machine of the other
+/// byte order would read them (`--endian`); nothing when `endian` is the host's.
+fn print_as_endian<const B: usize, const N: usize>(view: &MemoryView<N>, endian: Endian) {
+    if endian != Endian::HOST {
+        let (len, num, guard) = decode_fields::<B, N>(view, endian);
+        outln!("  as {}: len={len}, num={num}, guard=0x{guard:08X}", endian.name());
+    }
+}
+
+/// Say up front which byte order the field values are decoded in, when it isn't the host's.
+fn announce_endian(endian: Endian) {
+    if endian != Endian::HOST {
+        outln!("Field values are decoded as {}; this machine is {}.", endian.name(), Endian::HOST.name());
+        outln!("The hex dump still shows the bytes exactly as they sit in memory.\n");
+    }
+}
+
+/// `--changes-only`: stand in for the rows held back since the last one printed.
+fn note_quiet_writes(count: &mut usize) {
+    match std::mem::take(count) {
+        0 => {}
+        1 => outln!("       ... 1 write with no visible change ..."),
+        n => outln!("       ... {n} writes with no visible change ..."),
+    }
+}
+
+/// Banner for the moment `len` stops satisfying `len <= buffer_size`.
+///
+/// On a terminal this also waits for Enter so the presenter can talk about it;
+/// piped runs just print the banner and carry on.
+fn announce_invalid_len(len: u32, buffer_size: usize) {
+    outln!();
+    outln!("  ⚠ INVARIANT BROKEN: len is now {len} > {buffer_size}");
+    outln!("    Any safe code that slices buffer[..len] from here on will panic.");
+    outln!();
+    wait_for_enter();
+}
+
+/// The stack-protector question: does the guard still hold its sentinel?
+fn guard_verdict(guard: u32, expected: u32) -> String {
+    if guard == expected {
+        format!("GUARD INTACT (0x{guard:08X})")
+    } else {
+        color::changed_text(&format!("GUARD CORRUPTED (was 0x{expected:08X}, now 0x{guard:08X})"))
+    }
+}
+
+/// `--check-invariant`'s verdict: `Ok`, or the broken parts in the changed color.
+fn describe_invariant(result: Result<(), String>) -> String {
+    match result {
+        Ok(()) => "Ok".to_string(),
+        Err(broken) => color::changed_text(&format!("Err({broken})")),
+    }
+}
+
+/// Run the safe code under test, catching its panic so the demo can go on.
+///
+/// With `--no-catch` nothing is caught: a panic unwinds out of `main` like any
+/// other, with the usual message (and a backtrace under `RUST_BACKTRACE=1`),
+/// and the process exits with status 101.
+fn run_safe_code<T>(opts: &Options, f: impl FnOnce() -> T) -> std::thread::Result<T> {
+    if opts.no_catch {
+        // Everything printed so far should land before the panic message
+        output::flush();
+        Ok(f())
+    } else {
+        std::panic::catch_unwind(AssertUnwindSafe(f))
+    }
+}
+
+/// The text a caught panic was raised with, if it was a string.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("<non-string panic payload>")
+}
+
+/// Rasterize the current view into the frame directory, if `--frames` was given.
+///
+/// Called before `print_diff` so the frame sees the same classification as the
+/// row printed for this write.
+fn emit_frame<const N: usize>(writer: &mut Option<frames::FrameWriter>, view: &MemoryView<N>, prev: &[u8; N]) {
+    if let Some(writer) = writer {
+        if let Err(err) = writer.write(view, prev) {
+            eprintln!("error: failed to write frame: {err}");
+            std::process::exit(1);
+        }
+    }
+}
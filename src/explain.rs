@@ -0,0 +1,141 @@
+//! Longer explanations printed by the `--explain-*` flags.
+//!
+//! These are the parts of the lesson that can't be shown by corrupting bytes:
+//! they're about what the compiler is *allowed to assume*, which no amount of
+//! runtime output can prove. Where we can back a claim with a (sound)
+//! runtime check, we do.
+
+use std::panic::AssertUnwindSafe;
+
+use crate::{safe_sum_prefix, sum_fixed, Frame, BUFFER_SIZE};
+use crate::output::outln;
+
+/// Why `len`, `num`, and `guard` are wrapped in `UnsafeCell`.
+///
+/// The "without `UnsafeCell`" half can only be described, not run: the whole
+/// problem is that it's UB, so whatever it printed would prove nothing.
+pub fn unsafecell() {
+    let len_off = Frame::len_offset();
+
+    outln!("=======================================================");
+    outln!("   WHY UnsafeCell?");
+    outln!("=======================================================\n");
+
+    outln!("The demo writes to Frame.len (bytes {len_off}..{}) through a raw pointer", len_off + Frame::len_size());
+    outln!("while safe code holds `&Frame` and later reads `frame.len`.\n");
+
+    // ------------------------------------------------------------------------
+    // With UnsafeCell: mutation behind `&` is allowed, so this is well-defined
+    // ------------------------------------------------------------------------
+    let frame = Frame::new();
+    let shared: &Frame = &frame;
+    let before = shared.read_len_volatile();
+
+    // `UnsafeCell::get` hands out a `*mut u32` even from `&self`. Writing
+    // through it is sound as long as nobody holds a `&u32` into the cell.
+    unsafe { *shared.len.get() = 9 };
+    let after = shared.read_len_volatile();
+
+    outln!("WITH UnsafeCell<u32> (what Frame actually does):");
+    outln!("  let shared: &Frame = &frame;");
+    outln!("  read len through shared   -> {before}");
+    outln!("  *shared.len.get() = 9;       // raw write behind a shared reference");
+    outln!("  read len through shared   -> {after}");
+    outln!("  Well-defined: UnsafeCell tells the compiler `&Frame` does NOT mean");
+    outln!("  \"len is frozen\", so it must re-read memory and sees {after}.\n");
+
+    // ------------------------------------------------------------------------
+    // Without UnsafeCell: described only
+    // ------------------------------------------------------------------------
+    outln!("WITHOUT UnsafeCell (if Frame declared `len: u32`):");
+    outln!("  let shared: &Frame = &frame;");
+    outln!("  let a = shared.len;                       // compiler may keep this in a register");
+    outln!("  unsafe {{ *(ptr as *mut u32) = 9 }};        // mutate through a raw pointer");
+    outln!("  let b = shared.len;                       // may still be {before}!");
+    outln!("  A `&T` promises the pointee won't change while the reference is live");
+    outln!("  (unless it's inside an UnsafeCell). The write above breaks that promise,");
+    outln!("  so the program has UB: the compiler may reuse `a` for `b`, reorder the");
+    outln!("  write, or assume this code path never runs. No output from it would mean");
+    outln!("  anything, which is why we can't demonstrate it here.\n");
+
+    outln!("Why this matters for the demo:");
+    outln!("  safe_sum_prefix() takes `&Frame` and reads len to slice buffer[..len].");
+    outln!("  With a plain u32 field, the compiler could legally use len = {BUFFER_SIZE} from");
+    outln!("  Frame::new() and never notice the corruption. UnsafeCell (plus volatile");
+    outln!("  reads) is what makes the corrupted value the one safe code really sees.");
+}
+
+/// Why summing `buffer.iter()` beats summing `buffer[..len]`.
+///
+/// The codegen half is described rather than shown: the demo is built at
+/// opt-level 0, where nothing gets elided. The robustness half runs for real.
+pub fn elision() {
+    outln!("=======================================================");
+    outln!("   BOUNDS CHECKS: len-driven vs len-free");
+    outln!("=======================================================\n");
+
+    outln!("safe_sum_prefix():  frame.buffer[..len].iter().sum()");
+    outln!("  len is loaded from memory at runtime. The compiler can't know it's");
+    outln!("  <= {BUFFER_SIZE}, so the slice must be bounds-checked, and that check is the");
+    outln!("  only thing standing between a corrupted len and an out-of-bounds read.\n");
+
+    outln!("sum_fixed():        frame.buffer.iter().sum()");
+    outln!("  The iterator's length is the array's type: [u8; {BUFFER_SIZE}]. There is no");
+    outln!("  index to check, so an optimized build emits no bounds checks at all,");
+    outln!("  and no value stored anywhere in memory can make it read a sixth byte.\n");
+
+    // Corrupt len exactly the way the demo does, then run both
+    let mut frame = Frame::new();
+    frame.buffer = [1, 2, 3, 4, 5];
+    frame.write_len_volatile(0x0908);
+
+    outln!("With len corrupted to {}:", frame.read_len_volatile());
+    match std::panic::catch_unwind(AssertUnwindSafe(|| safe_sum_prefix(&frame))) {
+        Ok(sum) => outln!("  safe_sum_prefix() = {sum}"),
+        Err(_) => outln!("  safe_sum_prefix() PANICKED (the bounds check fired)"),
+    }
+    outln!("  sum_fixed()       = {} (len never consulted)\n", sum_fixed(&frame));
+
+    outln!("When the bound is known at compile time, use it: the len-free version is");
+    outln!("both faster (no checks) and immune to the corruption this demo is about.");
+    outln!("Runtime lengths are sometimes unavoidable, but each one is a value that");
+    outln!("has to be trusted.");
+}
+
+/// Whether `#[inline(always)]` on the volatile readers changes anything (it doesn't).
+pub fn inline() {
+    outln!("=======================================================");
+    outln!("   #[inline(always)] vs read_volatile");
+    outln!("=======================================================\n");
+
+    outln!("Frame's readers are `#[inline(always)]` wrappers around read_volatile.");
+    outln!("The two attributes answer different questions:\n");
+    outln!("  #[inline(always)]  WHERE the code goes: paste the body into the caller");
+    outln!("                     instead of emitting a call. A hint about codegen only.");
+    outln!("  read_volatile      WHAT the code does: perform exactly this load from");
+    outln!("                     memory, never cached, merged, or removed.\n");
+    outln!("Inlining can't weaken a volatile read: once pasted into the caller it is");
+    outln!("still a volatile read, and the optimizer must still perform it. And an");
+    outln!("out-of-line call wouldn't save a plain read either: the compiler can see");
+    outln!("through calls, so it's the volatile, not the call boundary, that matters.\n");
+
+    let mut frame = Frame::new();
+    let base_ptr: *mut u8 = (&mut frame as *mut Frame).cast::<u8>();
+    // The same 12-byte overflow the main demo ends with
+    for i in 0..12 {
+        unsafe { *base_ptr.add(i) = i as u8 };
+    }
+
+    outln!("After a 12-byte overflow, read both ways:");
+    outln!("  {:<6} {:>12} {:>12}", "field", "inlined", "outlined");
+    outln!("  {:<6} {:>12} {:>12}", "len", frame.read_len_volatile(), frame.read_len_volatile_outlined());
+    outln!("  {:<6} {:>12} {:>12}", "num", frame.read_num_volatile(), frame.read_num_volatile_outlined());
+    outln!(
+        "  {:<6} {:>12} {:>12}",
+        "guard",
+        format!("0x{:08X}", frame.read_guard_volatile()),
+        format!("0x{:08X}", frame.read_guard_volatile_outlined())
+    );
+    outln!();
+    outln!("Identical, as they must be: both see the corrupted bytes in memory.");
+}
@@ -0,0 +1,297 @@
+//! Rasterize `MemoryView` states into image files for building animated GIFs.
+//!
+//! Each frame is one row of colored cells (one per byte) using the same
+//! [`ByteKind`] categories as the terminal dump, with the byte offset drawn
+//! above each cell and the byte value inside it. Frames are numbered
+//! sequentially across the whole run (`frame_000.ppm`, `frame_001.ppm`, ...)
+//! so an external tool can assemble them directly, e.g.
+//! `convert -delay 50 frames/frame_*.ppm demo.gif`.
+//!
+//! PPM is written by default because it needs no encoder at all. PNG output
+//! is available behind the `png` feature.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::cli::FrameFormat;
+use crate::{ByteKind, MemoryView};
+
+/// Side length of one byte cell, in pixels
+const CELL: usize = 16;
+
+/// Gap between adjacent cells
+const GAP: usize = 2;
+
+/// Extra gap drawn where the text dump prints a `|` separator
+const SEP_GAP: usize = 6;
+
+/// Outer margin around the grid
+const MARGIN: usize = 6;
+
+/// Scale factor applied to the 3x5 font for offset labels
+const LABEL_SCALE: usize = 2;
+
+/// Height reserved above the cells for offset labels
+const LABEL_HEIGHT: usize = 5 * LABEL_SCALE + 4;
+
+const BACKGROUND: [u8; 3] = [0x1e, 0x1e, 0x1e];
+const LABEL_COLOR: [u8; 3] = [0xc0, 0xc0, 0xc0];
+const VALUE_COLOR: [u8; 3] = [0xff, 0xff, 0xff];
+
+/// Per-field fill colors for `--field-colors` (blue, yellow, magenta, cyan)
+const FIELD_RGB: [[u8; 3]; 4] = [
+    [0x26, 0x8b, 0xd2],
+    [0xb5, 0x89, 0x00],
+    [0xd3, 0x36, 0x82],
+    [0x2a, 0xa1, 0x98],
+];
+
+impl ByteKind {
+    /// Fill color used for this kind of byte in rasterized frames
+    fn rgb(self) -> [u8; 3] {
+        match self {
+            ByteKind::Changed => [0xdc, 0x32, 0x2f],
+            ByteKind::Watched => [0x2e, 0xa0, 0x43],
+            ByteKind::Read => [0x00, 0xc8, 0xd8],
+            ByteKind::Corrupted => [0x90, 0x90, 0x90],
+            ByteKind::Tripped => [0xff, 0xb0, 0x00],
+            ByteKind::Plain => [0x50, 0x50, 0x50],
+            ByteKind::Field(index) => FIELD_RGB[index % FIELD_RGB.len()],
+            ByteKind::Padding => [0x30, 0x30, 0x30],
+        }
+    }
+}
+
+/// Writes numbered image files into a directory, one per call to [`FrameWriter::write`].
+pub struct FrameWriter {
+    dir: PathBuf,
+    format: FrameFormat,
+    next: usize,
+}
+
+impl FrameWriter {
+    /// Create the output directory (if needed) and start numbering at zero.
+    pub fn new(dir: &Path, format: FrameFormat) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            format,
+            next: 0,
+        })
+    }
+
+    /// Rasterize the view's current snapshot, highlighting bytes that differ from `prev`.
+    pub fn write<const N: usize>(&mut self, view: &MemoryView<N>, prev: &[u8; N]) -> io::Result<()> {
+        let image = rasterize(view, prev);
+
+        let ext = match self.format {
+            FrameFormat::Ppm => "ppm",
+            FrameFormat::Png => "png",
+        };
+        let path = self.dir.join(format!("frame_{:03}.{ext}", self.next));
+        self.next += 1;
+
+        let bytes = match self.format {
+            FrameFormat::Ppm => image.to_ppm(),
+            #[cfg(feature = "png")]
+            FrameFormat::Png => png::encode(&image),
+            #[cfg(not(feature = "png"))]
+            FrameFormat::Png => unreachable!("png frames are rejected at argument parsing"),
+        };
+        fs::write(path, bytes)
+    }
+
+    /// Number of frames written so far
+    pub fn count(&self) -> usize {
+        self.next
+    }
+}
+
+// ============================================================================
+// RASTERIZATION
+// ============================================================================
+
+/// A simple RGB8 image buffer.
+struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    fn new(width: usize, height: usize, fill: [u8; 3]) -> Self {
+        Self {
+            width,
+            height,
+            pixels: fill.repeat(width * height),
+        }
+    }
+
+    fn fill_rect(&mut self, x: usize, y: usize, w: usize, h: usize, color: [u8; 3]) {
+        for row in y..(y + h).min(self.height) {
+            for col in x..(x + w).min(self.width) {
+                let at = (row * self.width + col) * 3;
+                self.pixels[at..at + 3].copy_from_slice(&color);
+            }
+        }
+    }
+
+    /// Draw a lowercase hex string using the built-in 3x5 font.
+    fn draw_hex(&mut self, x: usize, y: usize, text: &str, scale: usize, color: [u8; 3]) {
+        for (n, ch) in text.chars().enumerate() {
+            let Some(digit) = ch.to_digit(16) else { continue };
+            let glyph = FONT[digit as usize];
+            let gx = x + n * 4 * scale;
+            for (row, bits) in glyph.iter().enumerate() {
+                for col in 0..3 {
+                    if bits & (0b100 >> col) != 0 {
+                        self.fill_rect(gx + col * scale, y + row * scale, scale, scale, color);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Encode as binary PPM (P6).
+    fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        out.extend_from_slice(&self.pixels);
+        out
+    }
+}
+
+/// Lay out one row of cells: offset label above, byte value inside.
+fn rasterize<const N: usize>(view: &MemoryView<N>, prev: &[u8; N]) -> Image {
+    // x position of each cell, accounting for separator gaps
+    let mut xs = [0usize; N];
+    let mut x = MARGIN;
+    for (i, slot) in xs.iter_mut().enumerate() {
+        if view.is_separator(i) {
+            x += SEP_GAP;
+        }
+        *slot = x;
+        x += CELL + GAP;
+    }
+
+    let width = x - GAP + MARGIN;
+    let height = MARGIN + LABEL_HEIGHT + CELL + MARGIN;
+    let mut image = Image::new(width, height, BACKGROUND);
+
+    let cell_y = MARGIN + LABEL_HEIGHT;
+    for (i, &byte) in view.snapshot.iter().enumerate() {
+        let kind = view.classify(i, prev[i] != byte);
+        image.fill_rect(xs[i], cell_y, CELL, CELL, kind.rgb());
+
+        // Offset label: two hex digits centered over the cell
+        let label_w = 7 * LABEL_SCALE;
+        image.draw_hex(xs[i] + (CELL - label_w) / 2, MARGIN, &format!("{:02x}", i % 0x100), LABEL_SCALE, LABEL_COLOR);
+
+        // Byte value: two small hex digits centered in the cell
+        image.draw_hex(xs[i] + (CELL - 7) / 2, cell_y + (CELL - 5) / 2, &format!("{byte:02x}"), 1, VALUE_COLOR);
+    }
+
+    image
+}
+
+/// 3x5 bitmap font for hex digits `0-9a-f`; each row uses the low 3 bits.
+const FONT: [[u8; 5]; 16] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111], // 0
+    [0b010, 0b110, 0b010, 0b010, 0b111], // 1
+    [0b111, 0b001, 0b111, 0b100, 0b111], // 2
+    [0b111, 0b001, 0b111, 0b001, 0b111], // 3
+    [0b101, 0b101, 0b111, 0b001, 0b001], // 4
+    [0b111, 0b100, 0b111, 0b001, 0b111], // 5
+    [0b111, 0b100, 0b111, 0b101, 0b111], // 6
+    [0b111, 0b001, 0b001, 0b001, 0b001], // 7
+    [0b111, 0b101, 0b111, 0b101, 0b111], // 8
+    [0b111, 0b101, 0b111, 0b001, 0b111], // 9
+    [0b010, 0b101, 0b111, 0b101, 0b101], // a
+    [0b110, 0b101, 0b110, 0b101, 0b110], // b
+    [0b011, 0b100, 0b100, 0b100, 0b011], // c
+    [0b110, 0b101, 0b101, 0b101, 0b110], // d
+    [0b111, 0b100, 0b111, 0b100, 0b111], // e
+    [0b111, 0b100, 0b111, 0b100, 0b100], // f
+];
+
+// ============================================================================
+// PNG ENCODING (feature = "png")
+// ============================================================================
+
+/// Minimal PNG encoder: truecolor, no filtering, stored (uncompressed) deflate.
+///
+/// Frames are tiny, so compression isn't worth an extra dependency; every
+/// viewer and GIF tool reads stored-deflate PNGs just fine.
+#[cfg(feature = "png")]
+mod png {
+    use super::Image;
+
+    pub fn encode(image: &Image) -> Vec<u8> {
+        // Raw scanlines, each prefixed with filter type 0 (None)
+        let stride = image.width * 3;
+        let mut raw = Vec::with_capacity((stride + 1) * image.height);
+        for row in image.pixels.chunks(stride) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+
+        let mut ihdr = Vec::with_capacity(13);
+        ihdr.extend_from_slice(&(image.width as u32).to_be_bytes());
+        ihdr.extend_from_slice(&(image.height as u32).to_be_bytes());
+        ihdr.extend_from_slice(&[8, 2, 0, 0, 0]); // 8-bit RGB, deflate, no filter, no interlace
+
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+        chunk(&mut out, b"IHDR", &ihdr);
+        chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+        chunk(&mut out, b"IEND", &[]);
+        out
+    }
+
+    fn chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+        out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        let start = out.len();
+        out.extend_from_slice(kind);
+        out.extend_from_slice(data);
+        let crc = crc32(&out[start..]);
+        out.extend_from_slice(&crc.to_be_bytes());
+    }
+
+    /// Wrap `data` in a zlib stream made of stored deflate blocks.
+    fn zlib_stored(data: &[u8]) -> Vec<u8> {
+        let mut out = vec![0x78, 0x01];
+        let mut blocks = data.chunks(0xFFFF).peekable();
+        if blocks.peek().is_none() {
+            out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+        }
+        while let Some(block) = blocks.next() {
+            let last = blocks.peek().is_none();
+            out.push(last as u8);
+            let len = block.len() as u16;
+            out.extend_from_slice(&len.to_le_bytes());
+            out.extend_from_slice(&(!len).to_le_bytes());
+            out.extend_from_slice(block);
+        }
+        out.extend_from_slice(&adler32(data).to_be_bytes());
+        out
+    }
+
+    fn crc32(data: &[u8]) -> u32 {
+        let mut crc = 0xFFFF_FFFFu32;
+        for &byte in data {
+            crc ^= byte as u32;
+            for _ in 0..8 {
+                crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            }
+        }
+        !crc
+    }
+
+    fn adler32(data: &[u8]) -> u32 {
+        let (mut a, mut b) = (1u32, 0u32);
+        for &byte in data {
+            a = (a + byte as u32) % 65521;
+            b = (b + a) % 65521;
+        }
+        (b << 16) | a
+    }
+}
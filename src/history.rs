@@ -0,0 +1,42 @@
+//! `--history-export`: every printed snapshot, one JSON object per line.
+//!
+//! Unlike `--jsonl`, which streams events as they happen, this is a complete
+//! record of memory states meant for scrubbing through afterwards: each line
+//! is `{"test", "step", "label", "bytes": [...], "changed": [...]}`, with
+//! `step` counting from 0 within each test.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::json::Object;
+use crate::Snapshot;
+
+/// Appends snapshot histories to a newline-delimited JSON file.
+pub struct HistoryWriter {
+    file: BufWriter<File>,
+}
+
+impl HistoryWriter {
+    /// Create (or truncate) the export file.
+    pub fn create(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            file: BufWriter::new(File::create(path)?),
+        })
+    }
+
+    /// Write one test's snapshots, in the order they were printed.
+    pub fn write_test<const N: usize>(&mut self, test: usize, history: &[Snapshot<N>]) -> io::Result<()> {
+        for (step, snapshot) in history.iter().enumerate() {
+            let line = Object::new()
+                .field("test", test)
+                .field("step", step)
+                .field("label", &snapshot.label)
+                .field("bytes", &snapshot.bytes[..])
+                .field("changed", &snapshot.changed)
+                .finish();
+            writeln!(self.file, "{line}")?;
+        }
+        self.file.flush()
+    }
+}
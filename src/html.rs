@@ -0,0 +1,122 @@
+//! `--format html`: the hex dump as colored text in a `<pre>`, for slides and
+//! web pages.
+//!
+//! Rows come from each test's [`MemoryView::history`](crate::MemoryView::history),
+//! so every byte keeps the [`ByteKind`] the view classified it as when the row
+//! was printed: the page is the terminal dump with `<span style="color:...">`
+//! in place of ANSI escapes. Styles are inline, so a `--html-fragment` can be
+//! pasted into a page that has no stylesheet for it.
+
+use std::fmt::Write;
+
+use crate::{ByteKind, Snapshot};
+
+impl ByteKind {
+    /// Inline CSS for this kind of byte, or `None` for plain text
+    fn css(self) -> Option<&'static str> {
+        match self {
+            ByteKind::Changed => Some("color:#f14c4c;font-weight:bold"),
+            ByteKind::Watched => Some("color:#23d18b"),
+            ByteKind::Corrupted => Some("color:#808080"),
+            ByteKind::Tripped => Some("color:#000;background:#ffb000"),
+            ByteKind::Read => Some("color:#000;background:#29b8db"),
+            ByteKind::Plain => None,
+            ByteKind::Field(index) => Some(FIELD_CSS[index % FIELD_CSS.len()]),
+            ByteKind::Padding => Some("color:#666"),
+        }
+    }
+}
+
+/// `--field-colors` tints, in the terminal's order (blue, yellow, magenta, cyan)
+const FIELD_CSS: [&str; 4] = ["color:#3b8eea", "color:#f5f543", "color:#d670d6", "color:#29b8db"];
+
+/// Style of the `<pre>` itself: a dark terminal look that works on any page
+const PRE_STYLE: &str = "background:#1e1e1e;color:#c0c0c0;padding:1em;line-height:1.3;\
+                         font-family:ui-monospace,Menlo,Consolas,monospace";
+
+/// Collects every test's rows, then renders them as one `<pre>`.
+#[derive(Debug, Default)]
+pub struct HtmlDump {
+    text: String,
+}
+
+impl HtmlDump {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append one test: a heading, its rows in the order they were printed,
+    /// and how safe code fared (`outcome`).
+    pub fn add_test<const N: usize>(
+        &mut self,
+        write_len: usize,
+        separators: &[usize],
+        history: &[Snapshot<N>],
+        outcome: &str,
+    ) {
+        if !self.text.is_empty() {
+            self.text.push('\n');
+        }
+        let _ = writeln!(self.text, "TEST: Write {write_len} bytes starting at buffer[0]");
+        for snapshot in history {
+            let _ = write!(self.text, "{:<6} |", escape(&snapshot.label));
+            for (i, (&byte, &kind)) in snapshot.bytes.iter().zip(&snapshot.kinds).enumerate() {
+                if i > 0 && separators.contains(&i) {
+                    self.text.push_str(" |");
+                }
+                match kind.css() {
+                    Some(css) => {
+                        let _ = write!(self.text, " <span style=\"{css}\">{byte:02x}</span> ");
+                    }
+                    None => {
+                        let _ = write!(self.text, " {byte:02x} ");
+                    }
+                }
+            }
+            self.text.push('\n');
+        }
+        let _ = writeln!(self.text, "{}", escape(outcome));
+    }
+
+    /// Just the `<pre>`, ready to paste into an existing page.
+    pub fn fragment(&self) -> String {
+        format!("<pre style=\"{PRE_STYLE}\">\n{}</pre>\n", self.text)
+    }
+
+    /// A complete document that can be saved and opened directly.
+    pub fn document(&self, title: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n\
+             <body style=\"background:#1e1e1e;margin:0\">\n{}</body>\n</html>\n",
+            escape(title),
+            self.fragment()
+        )
+    }
+}
+
+/// Escape the characters that mean something in HTML text.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryView;
+
+    #[test]
+    fn rows_keep_the_views_classification() {
+        let mut view: MemoryView<3> = MemoryView::new(&[(2, 3)], &[2]);
+        view.render_diff(&[0; 3], "init");
+        view.snapshot = [0x00, 0x07, 0x00];
+        view.render_diff(&[0; 3], "i=1");
+
+        let mut dump = HtmlDump::new();
+        dump.add_test(2, &[2], view.history(), "safe_sum_prefix() = 7");
+        let html = dump.fragment();
+
+        assert!(html.contains("init   | 00  00  | <span style=\"color:#23d18b\">00</span> \n"));
+        assert!(html.contains("i=1    | 00  <span style=\"color:#f14c4c;font-weight:bold\">07</span>  |"));
+        assert!(html.ends_with("safe_sum_prefix() = 7\n</pre>\n"));
+    }
+}
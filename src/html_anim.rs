@@ -0,0 +1,167 @@
+//! `--html-anim`: a self-contained, animated HTML page of the whole run.
+//!
+//! Every printed snapshot is embedded as JSON (bytes, changed offsets, and the
+//! [`ByteKind`] each byte was drawn with), and a small inline script steps
+//! through them with play/pause/step controls. No server and no external
+//! files: the page can be mailed around or dropped into a course site.
+
+use std::fmt::Write;
+
+use crate::json::{Object, ToJson};
+use crate::layout::Layout;
+use crate::{ByteKind, Snapshot};
+
+impl ByteKind {
+    /// CSS class for this kind of byte in the HTML page
+    fn css_class(self) -> String {
+        match self {
+            ByteKind::Changed => "changed".to_string(),
+            ByteKind::Watched => "watched".to_string(),
+            ByteKind::Corrupted => "corrupted".to_string(),
+            ByteKind::Tripped => "tripped".to_string(),
+            ByteKind::Plain => "plain".to_string(),
+            ByteKind::Read => "read".to_string(),
+            ByteKind::Field(index) => format!("field{}", index % 4),
+            ByteKind::Padding => "padding".to_string(),
+        }
+    }
+}
+
+/// Collects snapshots from every test, then renders them as one page.
+#[derive(Debug, Default)]
+pub struct HtmlAnimation {
+    frames: Vec<String>,
+}
+
+impl HtmlAnimation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append one test's snapshots, in the order they were printed.
+    pub fn add_test<const N: usize>(&mut self, test: usize, write_len: usize, history: &[Snapshot<N>]) {
+        for snapshot in history {
+            let kinds: Vec<String> = snapshot.kinds.iter().map(|kind| kind.css_class()).collect();
+            let frame = Object::new()
+                .field("test", test)
+                .field("write_len", write_len)
+                .field("label", &snapshot.label)
+                .field("bytes", &snapshot.bytes[..])
+                .field("changed", &snapshot.changed)
+                .field("kinds", &kinds)
+                .finish();
+            self.frames.push(frame);
+        }
+    }
+
+    /// The complete HTML document.
+    pub fn render(&self, layout: &Layout) -> String {
+        let fields: Vec<Object> = layout
+            .fields
+            .iter()
+            .map(|f| Object::new().field("name", f.name).field("offset", f.offset).field("size", f.size))
+            .collect();
+
+        let mut data = String::new();
+        let _ = write!(data, "{{\"size\":{},\"fields\":", layout.size);
+        fields.write_json(&mut data);
+        let _ = write!(data, ",\"frames\":[{}]}}", self.frames.join(",\n"));
+
+        // The JSON can't contain `</script>`: strings are field names and labels we control
+        TEMPLATE.replace("/*DATA*/", &data).replace("/*TITLE*/", layout.name)
+    }
+}
+
+const TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>/*TITLE*/ corruption, step by step</title>
+<style>
+  body { background: #1e1e1e; color: #c0c0c0; font-family: ui-monospace, monospace; padding: 1.5em; }
+  h1 { font-size: 1.1em; color: #fff; }
+  #grid { display: flex; gap: 4px; margin: 1em 0; }
+  .cell { width: 2.4em; text-align: center; }
+  .offset { font-size: 0.75em; color: #888; }
+  .byte { padding: 0.5em 0; border-radius: 3px; color: #fff; }
+  .field-start { margin-left: 10px; }
+  .name { font-size: 0.75em; color: #888; height: 1.2em; }
+  .changed { background: #dc322f; }
+  .watched { background: #2ea043; }
+  .corrupted { background: #7a2e2c; }
+  .tripped { background: #ffb000; color: #000; }
+  .plain   { background: #505050; }
+  .read    { background: #00c8d8; color: #000; }
+  .padding { background: #303030; }
+  .field0 { background: #268bd2; } .field1 { background: #b58900; }
+  .field2 { background: #d33682; } .field3 { background: #2aa198; }
+  button { font: inherit; margin-right: 0.5em; }
+  #status { margin-top: 1em; }
+</style>
+</head>
+<body>
+<h1>/*TITLE*/: every write, one frame at a time</h1>
+<div>
+  <button id="prev">&#9664; step</button>
+  <button id="play">play</button>
+  <button id="next">step &#9654;</button>
+  <input id="scrub" type="range" min="0" value="0">
+</div>
+<div id="grid"></div>
+<div id="status"></div>
+<script>
+const DATA = /*DATA*/;
+
+const grid = document.getElementById("grid");
+const status = document.getElementById("status");
+const scrub = document.getElementById("scrub");
+const playButton = document.getElementById("play");
+scrub.max = DATA.frames.length - 1;
+
+const starts = new Map(DATA.fields.map(f => [f.offset, f.name]));
+const cells = [];
+for (let i = 0; i < DATA.size; i++) {
+  const cell = document.createElement("div");
+  cell.className = "cell" + (starts.has(i) && i > 0 ? " field-start" : "");
+  cell.innerHTML = `<div class="name">${starts.get(i) ?? ""}</div>` +
+    `<div class="offset">${i.toString(16).padStart(2, "0")}</div><div class="byte"></div>`;
+  grid.appendChild(cell);
+  cells.push(cell.querySelector(".byte"));
+}
+
+let current = 0;
+let timer = null;
+
+function show(n) {
+  current = Math.max(0, Math.min(n, DATA.frames.length - 1));
+  const frame = DATA.frames[current];
+  frame.bytes.forEach((b, i) => {
+    cells[i].textContent = b.toString(16).padStart(2, "0");
+    cells[i].className = "byte " + frame.kinds[i];
+  });
+  scrub.value = current;
+  status.textContent = `test ${frame.test} (write ${frame.write_len} bytes), ${frame.label}` +
+    `  [frame ${current + 1}/${DATA.frames.length}]`;
+}
+
+function pause() {
+  clearInterval(timer);
+  timer = null;
+  playButton.textContent = "play";
+}
+
+function play() {
+  if (current === DATA.frames.length - 1) show(0);
+  timer = setInterval(() => current < DATA.frames.length - 1 ? show(current + 1) : pause(), 400);
+  playButton.textContent = "pause";
+}
+
+playButton.onclick = () => timer ? pause() : play();
+document.getElementById("prev").onclick = () => { pause(); show(current - 1); };
+document.getElementById("next").onclick = () => { pause(); show(current + 1); };
+scrub.oninput = () => { pause(); show(Number(scrub.value)); };
+show(0);
+</script>
+</body>
+</html>
+"#;
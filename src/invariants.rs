@@ -0,0 +1,90 @@
+//! `--verify-invariants`: check the assumptions the narrative rests on.
+//!
+//! The whole demo is "safe code trusts `len <= BUFFER_SIZE`, unsafe code
+//! breaks it". That story only holds if a fresh `Frame` really starts out the
+//! way the docs say. These checks run before any corruption so that if
+//! `Frame::new` ever drifts, the demo says so instead of quietly teaching the
+//! wrong thing.
+
+use crate::output::outln;
+use crate::{safe_sum_prefix, Frame, BUFFER_SIZE};
+
+/// One documented assumption and whether a pristine `Frame` satisfies it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invariant {
+    /// The claim, as the docs state it
+    pub claim: &'static str,
+
+    /// What a pristine frame actually holds, for the printout
+    pub observed: String,
+
+    pub holds: bool,
+}
+
+/// Evaluate every invariant against a fresh `Frame::new()`.
+pub fn check() -> Vec<Invariant> {
+    let frame = Frame::new();
+    let len = frame.read_len_volatile();
+    let guard = frame.read_guard_volatile();
+    let buffer_sum: u64 = frame.buffer.iter().map(|&b| b as u64).sum();
+
+    // Only call the consumer once the slice is known to be in bounds
+    let safe_sum = (len as usize <= BUFFER_SIZE).then(|| safe_sum_prefix(&frame));
+
+    vec![
+        Invariant {
+            claim: "len <= BUFFER_SIZE",
+            observed: format!("len = {len}"),
+            holds: len as usize <= BUFFER_SIZE,
+        },
+        Invariant {
+            claim: "len == BUFFER_SIZE",
+            observed: format!("len = {len}, BUFFER_SIZE = {BUFFER_SIZE}"),
+            holds: len as usize == BUFFER_SIZE,
+        },
+        Invariant {
+            claim: "guard == 0xDEAD_BEEF",
+            observed: format!("guard = 0x{guard:08X}"),
+            holds: guard == 0xDEAD_BEEF,
+        },
+        Invariant {
+            claim: "safe_sum_prefix == sum(buffer)",
+            observed: match safe_sum {
+                Some(sum) => format!("{sum} vs {buffer_sum}"),
+                None => "skipped, len is out of bounds".to_string(),
+            },
+            holds: safe_sum == Some(buffer_sum),
+        },
+    ]
+}
+
+/// Print each invariant with PASS/FAIL; returns whether all of them hold.
+pub fn verify() -> bool {
+    outln!("Checking the invariants the demo relies on (pristine Frame::new()):");
+
+    let invariants = check();
+    for invariant in &invariants {
+        let verdict = if invariant.holds { "PASS" } else { "FAIL" };
+        outln!("  [{verdict}] {:<32} ({})", invariant.claim, invariant.observed);
+    }
+
+    let all_hold = invariants.iter().all(|invariant| invariant.holds);
+    if all_hold {
+        outln!("All invariants hold; any later violation is the unsafe code's doing.\n");
+    } else {
+        outln!("Frame::new() no longer matches what the demo claims!\n");
+    }
+    all_hold
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pristine_frame_satisfies_every_invariant() {
+        for invariant in check() {
+            assert!(invariant.holds, "{} failed: {}", invariant.claim, invariant.observed);
+        }
+    }
+}
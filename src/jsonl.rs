@@ -0,0 +1,88 @@
+//! `--jsonl`: stream demo events as JSON Lines while they happen.
+//!
+//! Every event is one compact JSON object on its own line, written straight
+//! to stdout and flushed immediately, so `tail -f` or a socket reader sees the
+//! corruption unfold in real time (paced by `--delay`, if given). The
+//! human-readable output is silenced in this mode so the stream stays clean.
+//!
+//! Every line carries:
+//!
+//! - `seq`: a sequence number, increasing by one per line across the whole run
+//! - `test`: index of the test (write length) the event belongs to
+//! - `event`: one of `test_start`, `write`, `read`, `panic`, `canary_check`, `result`
+
+use std::io::{self, Write};
+
+use crate::json::{Object, ToJson};
+
+/// Numbers and writes events for one run.
+#[derive(Debug, Default)]
+pub struct EventStream {
+    seq: u64,
+    test: usize,
+}
+
+impl EventStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a new test; later events carry its index.
+    pub fn start_test(&mut self, test: usize, write_len: usize) {
+        self.test = test;
+        self.emit("test_start", &[("write_len", &write_len)]);
+    }
+
+    /// One unchecked byte write, and the offsets it changed.
+    pub fn write(&mut self, index: usize, offset: usize, value: u8, field: Option<&str>, changed: &[usize]) {
+        self.emit(
+            "write",
+            &[
+                ("index", &index),
+                ("offset", &offset),
+                ("value", &value),
+                ("field", &field),
+                ("changed", &changed),
+            ],
+        );
+    }
+
+    /// Safe code is about to read `buffer[index]`.
+    pub fn read(&mut self, index: usize) {
+        self.emit("read", &[("index", &index)]);
+    }
+
+    /// Safe code panicked.
+    pub fn panic(&mut self, message: &str) {
+        self.emit("panic", &[("message", &message)]);
+    }
+
+    /// The guard sentinel, and whether it still holds its initial value.
+    pub fn canary_check(&mut self, guard: u32, expected: u32) {
+        self.emit(
+            "canary_check",
+            &[("guard", &guard), ("expected", &expected), ("intact", &(guard == expected))],
+        );
+    }
+
+    /// How the test ended: the sum safe code computed, or `null` if it panicked.
+    pub fn result(&mut self, len: u32, sum: Option<u64>) {
+        self.emit("result", &[("len", &len), ("sum", &sum), ("panicked", &sum.is_none())]);
+    }
+
+    fn emit(&mut self, event: &str, fields: &[(&str, &dyn ToJson)]) {
+        let mut object = Object::new()
+            .field("seq", self.seq)
+            .field("test", self.test)
+            .field("event", event);
+        for &(key, value) in fields {
+            object = object.field(key, value);
+        }
+        self.seq += 1;
+
+        let mut stdout = io::stdout().lock();
+        // A reader that went away isn't worth a crash; the run just ends unobserved
+        let _ = writeln!(stdout, "{}", object.finish());
+        let _ = stdout.flush();
+    }
+}
@@ -0,0 +1,213 @@
+//! Compile-time layout descriptions of the victim structs.
+//!
+//! Each [`Layout`] records where every field lives (via `offset_of!`), how big
+//! it is, and how aligned it wants to be. Everything that needs to know "which
+//! byte belongs to which field" or "where is the padding" should ask a
+//! `Layout` rather than recomputing offsets by hand.
+
+use std::mem::{align_of, offset_of, size_of};
+
+use crate::{Frame, PackedFrame, ReorderFrame, SizedFrame};
+use crate::output::outln;
+
+/// One field of a victim struct.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldInfo {
+    pub name: &'static str,
+    pub offset: usize,
+    pub size: usize,
+
+    /// The field type's natural alignment (not necessarily honored, see `repr(packed)`)
+    pub align: usize,
+}
+
+impl FieldInfo {
+    /// One past the field's last byte
+    pub const fn end(&self) -> usize {
+        self.offset + self.size
+    }
+
+    /// Does the field sit at an offset its type's alignment allows?
+    pub const fn is_aligned(&self) -> bool {
+        self.offset.is_multiple_of(self.align)
+    }
+}
+
+/// The layout of a whole victim struct.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    /// Struct name, for printing
+    pub name: &'static str,
+
+    /// The `repr` attribute the struct uses, for printing
+    pub repr: &'static str,
+
+    pub size: usize,
+    pub align: usize,
+
+    /// Fields in declaration order
+    pub fields: &'static [FieldInfo],
+}
+
+/// Describe a field: `field!(Struct, name: Type)`
+///
+/// Callers need `FieldInfo`, `offset_of`, `size_of`, and `align_of` in scope.
+macro_rules! field {
+    ($owner:ty, $name:ident: $ty:ty) => {
+        FieldInfo {
+            name: stringify!($name),
+            offset: offset_of!($owner, $name),
+            size: size_of::<$ty>(),
+            align: align_of::<$ty>(),
+        }
+    };
+}
+pub(crate) use field;
+
+impl<const B: usize> SizedFrame<B> {
+    /// The layout of this size of frame
+    pub const LAYOUT: &'static Layout = &Layout {
+        name: "Frame",
+        repr: "repr(C)",
+        size: size_of::<Self>(),
+        align: align_of::<Self>(),
+        fields: &[
+            field!(Self, buffer: [u8; B]),
+            field!(Self, len: u32),
+            field!(Self, num: i32),
+            field!(Self, guard: u32),
+        ],
+    };
+}
+
+/// The main demo struct
+pub const FRAME: Layout = *Frame::LAYOUT;
+
+/// Same fields as `Frame`, packed: no padding, but misaligned integers
+pub const PACKED_FRAME: Layout = Layout {
+    name: "PackedFrame",
+    repr: "repr(C, packed)",
+    size: size_of::<PackedFrame>(),
+    align: align_of::<PackedFrame>(),
+    fields: &[
+        field!(PackedFrame, buffer: [u8; crate::BUFFER_SIZE]),
+        field!(PackedFrame, len: u32),
+        field!(PackedFrame, num: i32),
+        field!(PackedFrame, guard: u32),
+    ],
+};
+
+/// Same fields as `Frame` with the default representation: order not guaranteed
+pub const REORDER_FRAME: Layout = Layout {
+    name: "ReorderFrame",
+    repr: "repr(Rust)",
+    size: size_of::<ReorderFrame>(),
+    align: align_of::<ReorderFrame>(),
+    fields: &[
+        field!(ReorderFrame, buffer: [u8; crate::BUFFER_SIZE]),
+        field!(ReorderFrame, len: u32),
+        field!(ReorderFrame, num: i32),
+        field!(ReorderFrame, guard: u32),
+    ],
+};
+
+/// Every victim struct variant, for side-by-side comparison
+pub const VARIANTS: &[Layout] = &[FRAME, PACKED_FRAME, REORDER_FRAME, crate::scenarios::multi_buffer::LAYOUT];
+
+impl Layout {
+    /// Does the first field start at offset 0, with each later field after the one before it?
+    ///
+    /// `repr(C)` guarantees this; `repr(Rust)` doesn't.
+    pub fn in_declaration_order(&self) -> bool {
+        self.fields.first().is_none_or(|f| f.offset == 0)
+            && self.fields.windows(2).all(|pair| pair[0].end() <= pair[1].offset)
+    }
+
+    /// Fields sorted by where they actually sit in memory
+    pub fn fields_by_offset(&self) -> Vec<FieldInfo> {
+        let mut fields = self.fields.to_vec();
+        fields.sort_by_key(|f| f.offset);
+        fields
+    }
+
+    /// Byte ranges `[start, end)` not covered by any field, including trailing padding.
+    pub fn padding(&self) -> Vec<(usize, usize)> {
+        let mut gaps = Vec::new();
+        let mut cursor = 0;
+        for field in self.fields_by_offset() {
+            if field.offset > cursor {
+                gaps.push((cursor, field.offset));
+            }
+            cursor = cursor.max(field.end());
+        }
+        if self.size > cursor {
+            gaps.push((cursor, self.size));
+        }
+        gaps
+    }
+
+    /// Index into `fields` of the field containing byte `offset` (`None` for padding)
+    pub fn field_index_at(&self, offset: usize) -> Option<usize> {
+        self.fields
+            .iter()
+            .position(|f| offset >= f.offset && offset < f.end())
+    }
+
+    /// The field called `name`, if the struct has one
+    pub fn field(&self, name: &str) -> Option<&FieldInfo> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    /// Total number of padding bytes
+    pub fn padding_bytes(&self) -> usize {
+        self.padding().iter().map(|(start, end)| end - start).sum()
+    }
+}
+
+/// Print a table comparing the size, alignment, and padding of every variant.
+pub fn print_sizes_table() {
+    outln!("Victim struct variants (all sizes in bytes):");
+    outln!();
+    outln!("  {:<14} {:<16} {:>4} {:>5}  padding", "struct", "repr", "size", "align");
+    outln!("  {:<14} {:<16} {:>4} {:>5}  -------", "------", "----", "----", "-----");
+
+    for layout in VARIANTS {
+        let gaps = layout.padding();
+        let padding = if gaps.is_empty() {
+            "none".to_string()
+        } else {
+            let ranges: Vec<String> = gaps.iter().map(|(s, e)| format!("[{s}..{e})")).collect();
+            format!("{} at {}", layout.padding_bytes(), ranges.join(", "))
+        };
+
+        outln!(
+            "  {:<14} {:<16} {:>4} {:>5}  {}",
+            layout.name, layout.repr, layout.size, layout.align, padding
+        );
+
+        let misaligned: Vec<String> = layout
+            .fields
+            .iter()
+            .filter(|f| !f.is_aligned())
+            .map(|f| format!("{} @ {} (wants align {})", f.name, f.offset, f.align))
+            .collect();
+        if !misaligned.is_empty() {
+            outln!("  {:<14} misaligned: {}", "", misaligned.join(", "));
+        }
+
+        if !layout.in_declaration_order() {
+            let order: Vec<String> = layout
+                .fields_by_offset()
+                .iter()
+                .map(|f| format!("{} @ {}", f.name, f.offset))
+                .collect();
+            outln!("  {:<14} reordered: {}", "", order.join(", "));
+        }
+    }
+
+    outln!();
+    outln!("repr(packed) removes the padding, shrinking the struct, but leaves the");
+    outln!("integer fields misaligned: taking a reference to one of them is UB.");
+    outln!("repr(Rust) is free to reorder fields; the demo depends on buffer coming");
+    outln!("first, so Frame is repr(C) and main() asserts the order before running.");
+}
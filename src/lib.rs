@@ -0,0 +1,2442 @@
+//! # Unsafe Memory Corruption Demo
+//!
+//! This demo shows how `unsafe` code can bypass Rust's safety guarantees and corrupt
+//! data that safe code depends on. It's designed to be educational and work in both
+//! the Rust Playground and local terminals.
+//!
+//! ## What This Demonstrates
+//!
+//! 1. Safe Rust code relies on invariants (e.g., "len <= buffer.len()")
+//! 2. Unsafe code can violate these invariants by writing out of bounds
+//! 3. When safe code later runs, it trusts the corrupted data and panics/misbehaves
+//!
+//! ## Why This Matters
+//!
+//! In real programs, this kind of bug can lead to:
+//! - Security vulnerabilities (buffer overflows)
+//! - Data corruption
+//! - Undefined behavior
+//! - Hard-to-debug crashes far from the actual bug
+//!
+//! ## Using It as a Library
+//!
+//! The binary is a thin driver over this crate. [`Frame`], [`MemoryView`],
+//! [`safe_sum_prefix`], and the [`color`] module are public, so exercises and
+//! integration tests can build their own corruptions, e.g. by writing through a
+//! raw pointer at [`Frame::len_offset`] and checking what `safe_sum_prefix`
+//! does with the result. Other structs can be dumped the same way by
+//! implementing [`victim::Victim`] for them.
+
+// ============================================================================
+// COLOR OUTPUT MODULE
+// ============================================================================
+
+/// Provides colored/marked output for the hex dump visualization.
+///
+/// Automatically detects if stdout is a terminal:
+/// - Terminal: uses ANSI escape codes for red (changed), green (watched),
+///   gray (changed earlier), and black on yellow (a sentinel that no longer
+///   holds its value)
+/// - Not a terminal (playground, pipe, file): uses [brackets], (parens), <angles>,
+///   and !bangs!
+///
+/// The environment can override the detection, in this order of precedence:
+///
+/// 1. `CLICOLOR_FORCE` set to anything but `0`: ANSI on, even into a pipe
+/// 2. `NO_COLOR` set and non-empty: ANSI off, even on a terminal
+/// 3. Otherwise, ANSI exactly when stdout is a terminal
+///
+/// An explicit `--color always|never` (the theme's `ansi`) beats all three.
+///
+/// # Themes
+///
+/// The actual colors and markers come from a [`Theme`](color::Theme). There is
+/// one process-wide theme, changed with `set_theme`; it affects every thread.
+/// Tests (which run in parallel) should use `with_theme` instead, which
+/// overrides the theme for the current thread only.
+///
+/// The styles for the diff view's byte states (changed, watched, corrupted,
+/// padding, plain) live in the theme's [`ColorScheme`](color::ColorScheme), so
+/// a palette can be swapped without touching the view logic.
+pub mod color {
+    use std::cell::Cell;
+    use std::ffi::OsStr;
+    use std::io::{stdout, IsTerminal};
+    use std::sync::{OnceLock, PoisonError, RwLock};
+
+    use crate::ByteKind;
+
+    /// How one category of byte is drawn.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Style {
+        /// ANSI SGR code used on terminals (e.g. `"91"` for bright red)
+        pub ansi: &'static str,
+
+        /// Characters wrapped around the byte when ANSI is off
+        pub markers: (char, char),
+    }
+
+    impl Style {
+        /// `xx` between the markers, for legends
+        pub fn sample(&self) -> String {
+            let (open, close) = self.markers;
+            format!("{open}xx{close}")
+        }
+    }
+
+    /// The styles for each state a byte can be in between two snapshots.
+    ///
+    /// A style with an empty `ansi` code is never colored, only padded like
+    /// `plain`. A scheme that colors nothing at all (see
+    /// [`monochrome`](ColorScheme::monochrome)) is drawn with its markers even
+    /// on a terminal, since markers are then the only way to tell bytes apart.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ColorScheme {
+        /// Bytes that changed this iteration
+        pub changed: Style,
+
+        /// Watched bytes that haven't been corrupted yet
+        pub watched: Style,
+
+        /// Bytes corrupted in an earlier iteration that didn't change this one
+        pub corrupted: Style,
+
+        /// Bytes no field owns
+        pub padding: Style,
+
+        /// Everything else
+        pub plain: Style,
+    }
+
+    impl ColorScheme {
+        /// Red for changes, green for watched fields, gray for old damage, dim for padding
+        pub const DEFAULT: ColorScheme = ColorScheme {
+            changed: Style { ansi: "91", markers: ('[', ']') },
+            watched: Style { ansi: "92", markers: ('(', ')') },
+            corrupted: Style { ansi: "90", markers: ('~', '~') },
+            padding: Style { ansi: "2", markers: ('{', '}') },
+            plain: Style { ansi: "", markers: (' ', ' ') },
+        };
+
+        /// Blue and orange instead of red and green, which deuteranopia
+        /// (the commonest color blindness) can't tell apart.
+        ///
+        /// ANSI codes: changed is 33 (orange/yellow), watched is 94 (bright
+        /// blue), corrupted is 90 (gray), and padding is 2 (dim). The markers differ too,
+        /// `[changed]` vs `<watched>`, so piped output keeps the distinction;
+        /// old damage moves to `«corrupted»` to stay unambiguous.
+        pub const fn colorblind() -> Self {
+            ColorScheme {
+                changed: Style { ansi: "33", markers: ('[', ']') },
+                watched: Style { ansi: "94", markers: ('<', '>') },
+                corrupted: Style { ansi: "90", markers: ('«', '»') },
+                padding: Style { ansi: "2", markers: ('{', '}') },
+                plain: Style { ansi: "", markers: (' ', ' ') },
+            }
+        }
+
+        /// No color anywhere: `[changed]`, `(watched)`, `~corrupted~`, `{padding}`
+        pub const fn monochrome() -> Self {
+            ColorScheme {
+                changed: Style { ansi: "", markers: ('[', ']') },
+                watched: Style { ansi: "", markers: ('(', ')') },
+                corrupted: Style { ansi: "", markers: ('~', '~') },
+                padding: Style { ansi: "", markers: ('{', '}') },
+                plain: Style { ansi: "", markers: (' ', ' ') },
+            }
+        }
+
+        /// Does any style in the scheme have a color?
+        fn is_colored(&self) -> bool {
+            [self.changed, self.watched, self.corrupted, self.padding, self.plain]
+                .iter()
+                .any(|style| !style.ansi.is_empty())
+        }
+    }
+
+    impl Default for ColorScheme {
+        fn default() -> Self {
+            Self::DEFAULT
+        }
+    }
+
+    /// The full set of styles used by the hex dump.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Theme {
+        /// Force ANSI on (`Some(true)`) or off (`Some(false)`); `None` auto-detects
+        pub ansi: Option<bool>,
+
+        /// How changed, watched, corrupted, padding, and plain bytes are drawn
+        pub scheme: ColorScheme,
+
+        /// The byte safe code is reading right now (`--highlight-reads`)
+        pub read: Style,
+
+        /// Bytes of a sentinel (the guard) that no longer holds its expected value
+        pub tripped: Style,
+    }
+
+    impl Theme {
+        /// The default color scheme, black on cyan for reads, black on yellow
+        /// for a dead canary, auto-detected ANSI
+        pub const DEFAULT: Theme = Theme {
+            ansi: None,
+            scheme: ColorScheme::DEFAULT,
+            read: Style { ansi: "30;106", markers: ('>', '<') },
+            tripped: Style { ansi: "30;103", markers: ('!', '!') },
+        };
+    }
+
+    impl Default for Theme {
+        fn default() -> Self {
+            Self::DEFAULT
+        }
+    }
+
+    /// The process-wide theme. Behind a lock because tests run in parallel.
+    static THEME: RwLock<Theme> = RwLock::new(Theme::DEFAULT);
+
+    thread_local! {
+        /// Per-thread override installed by `with_theme`
+        static OVERRIDE: Cell<Option<Theme>> = const { Cell::new(None) };
+    }
+
+    /// The theme in effect for the current thread.
+    pub fn theme() -> Theme {
+        OVERRIDE
+            .with(Cell::get)
+            .unwrap_or_else(|| *THEME.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Replace the process-wide theme. Affects every thread without a `with_theme` override.
+    pub fn set_theme(theme: Theme) {
+        *THEME.write().unwrap_or_else(PoisonError::into_inner) = theme;
+    }
+
+    /// Run `f` with `theme` in effect on this thread only, restoring the
+    /// previous theme afterwards (even if `f` panics).
+    pub fn with_theme<R>(theme: Theme, f: impl FnOnce() -> R) -> R {
+        struct Restore(Option<Theme>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                OVERRIDE.with(|o| o.set(self.0));
+            }
+        }
+
+        let _restore = Restore(OVERRIDE.with(|o| o.replace(Some(theme))));
+        f()
+    }
+
+    /// Cached result of environment and terminal detection (checked once at startup)
+    fn detected_ansi() -> bool {
+        static DETECTED: OnceLock<bool> = OnceLock::new();
+        *DETECTED.get_or_init(|| {
+            let force = std::env::var_os("CLICOLOR_FORCE");
+            let no_color = std::env::var_os("NO_COLOR");
+            ansi_from_env(force.as_deref(), no_color.as_deref(), || stdout().is_terminal())
+        })
+    }
+
+    /// The precedence rules from the module docs, with the TTY check only run if needed
+    fn ansi_from_env(force: Option<&OsStr>, no_color: Option<&OsStr>, is_tty: impl FnOnce() -> bool) -> bool {
+        if force.is_some_and(|v| !v.is_empty() && v != "0") {
+            true
+        } else if no_color.is_some_and(|v| !v.is_empty()) {
+            false
+        } else {
+            is_tty()
+        }
+    }
+
+    /// Should we emit ANSI escapes? The theme can force it either way, and a
+    /// scheme without colors always gets markers.
+    fn use_ansi() -> bool {
+        let theme = theme();
+        theme.scheme.is_colored() && theme.ansi.unwrap_or_else(detected_ansi)
+    }
+
+    /// Format a byte with the given style
+    pub(crate) fn paint(style: Style, byte: u8) -> String {
+        paint_cell(style, &format!("{byte:02x}"))
+    }
+
+    /// One cell of the dump (a byte's hex, or a whole word's) in a style:
+    /// wrapped in its markers, or padded by a space either side on a terminal.
+    fn paint_cell(style: Style, text: &str) -> String {
+        if !use_ansi() {
+            let (open, close) = style.markers;
+            format!("{open}{text}{close}")
+        } else if style.ansi.is_empty() {
+            format!(" {text} ")
+        } else {
+            format!(" \x1b[{}m{text}\x1b[0m ", style.ansi)
+        }
+    }
+
+    /// Format a byte that changed this iteration (red / [bracketed])
+    pub fn red(byte: u8) -> String {
+        paint(theme().scheme.changed, byte)
+    }
+
+    /// Format a watched byte that hasn't been corrupted yet (green / (parens))
+    pub fn green(byte: u8) -> String {
+        paint(theme().scheme.watched, byte)
+    }
+
+    /// Format a byte corrupted earlier that didn't change this time (gray / ~tilded~)
+    pub fn gray(byte: u8) -> String {
+        paint(theme().scheme.corrupted, byte)
+    }
+
+    /// Format a byte of a sentinel that no longer matches (black on yellow / !banged!)
+    pub fn tripped(byte: u8) -> String {
+        paint(theme().tripped, byte)
+    }
+
+    /// Format the byte currently being read (black on cyan / >angled<)
+    pub fn read(byte: u8) -> String {
+        paint(theme().read, byte)
+    }
+
+    /// Format a plain byte (no special highlighting)
+    pub fn plain(byte: u8) -> String {
+        paint(theme().scheme.plain, byte)
+    }
+
+    /// One character of the ASCII sidebar, highlighted like its byte.
+    ///
+    /// On a terminal that's the bare character in color. With markers it's
+    /// wrapped like the hex (`[A]`), and plain characters are padded to the
+    /// same width so the column stays aligned.
+    pub(crate) fn ascii(kind: ByteKind, byte: u8) -> String {
+        let ch = if (0x20..=0x7e).contains(&byte) { byte as char } else { '.' };
+        paint_text(style_of(kind), &ch.to_string())
+    }
+
+    /// One cell of the dump in the style for `kind`: a `--view words` word,
+    /// or a legend's sample.
+    pub(crate) fn cell(kind: ByteKind, text: &str) -> String {
+        paint_cell(style_of(kind), text)
+    }
+
+    /// The current theme's style for a byte category
+    fn style_of(kind: ByteKind) -> Style {
+        let theme = theme();
+        match kind {
+            ByteKind::Changed => theme.scheme.changed,
+            ByteKind::Watched => theme.scheme.watched,
+            ByteKind::Read => theme.read,
+            ByteKind::Corrupted => theme.scheme.corrupted,
+            ByteKind::Tripped => theme.tripped,
+            ByteKind::Field(index) => FIELD_STYLES[index % FIELD_STYLES.len()],
+            ByteKind::Padding => theme.scheme.padding,
+            ByteKind::Plain => theme.scheme.plain,
+        }
+    }
+
+    /// One style per field for `--field-colors`, cycled if a struct has more fields
+    const FIELD_STYLES: [Style; 4] = [
+        Style { ansi: "94", markers: ('<', '>') }, // blue
+        Style { ansi: "93", markers: ('[', ']') }, // yellow
+        Style { ansi: "95", markers: ('(', ')') }, // magenta
+        Style { ansi: "96", markers: ('«', '»') }, // cyan
+    ];
+
+    /// Format a byte tinted by the index of the field that owns it
+    pub fn field(index: usize, byte: u8) -> String {
+        paint(FIELD_STYLES[index % FIELD_STYLES.len()], byte)
+    }
+
+    /// Format a padding byte (gray / {braced})
+    pub fn padding(byte: u8) -> String {
+        paint(theme().scheme.padding, byte)
+    }
+
+    /// A field name in that field's color (or markers), for legends
+    pub fn field_label(index: usize, name: &str) -> String {
+        paint_text(FIELD_STYLES[index % FIELD_STYLES.len()], name)
+    }
+
+    /// The word "padding" in the padding color (or markers), for legends
+    pub fn padding_label() -> String {
+        paint_text(theme().scheme.padding, "padding")
+    }
+
+    /// Wrap text in a style's color, or its markers when ANSI is off
+    fn paint_text(style: Style, text: &str) -> String {
+        if !use_ansi() {
+            let (open, close) = style.markers;
+            format!("{open}{text}{close}")
+        } else if style.ansi.is_empty() {
+            text.to_string()
+        } else {
+            format!("\x1b[{}m{text}\x1b[0m", style.ansi)
+        }
+    }
+
+    /// Highlight arbitrary text in the "changed" color (unchanged when not a terminal)
+    pub fn changed_text(text: &str) -> String {
+        let ansi = theme().scheme.changed.ansi;
+        if use_ansi() && !ansi.is_empty() {
+            format!("\x1b[{ansi}m{text}\x1b[0m")
+        } else {
+            text.to_string()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const ANGLE: Theme = Theme {
+            ansi: Some(false),
+            scheme: ColorScheme {
+                changed: Style { ansi: "94", markers: ('<', '>') },
+                ..ColorScheme::DEFAULT
+            },
+            ..Theme::DEFAULT
+        };
+
+        #[test]
+        fn with_theme_overrides_only_the_current_thread() {
+            with_theme(ANGLE, || {
+                assert_eq!(red(0x41), "<41>");
+
+                // Another thread keeps rendering with the process-wide theme
+                let other = std::thread::spawn(theme).join().unwrap();
+                assert_eq!(other, *THEME.read().unwrap());
+            });
+
+            assert_ne!(theme(), ANGLE);
+        }
+
+        #[test]
+        fn clicolor_force_beats_no_color_beats_tty() {
+            let os = |s| Some(OsStr::new(s));
+
+            assert!(ansi_from_env(os("1"), os("1"), || false));
+            assert!(!ansi_from_env(None, os("1"), || true));
+            assert!(!ansi_from_env(os("0"), os("1"), || true));
+            // Empty means unset for both
+            assert!(ansi_from_env(os(""), os(""), || true));
+            assert!(!ansi_from_env(None, None, || false));
+        }
+
+        #[test]
+        fn a_monochrome_scheme_keeps_its_markers_on_a_terminal() {
+            let mono = Theme {
+                ansi: Some(true),
+                scheme: ColorScheme::monochrome(),
+                ..Theme::DEFAULT
+            };
+            with_theme(mono, || {
+                assert_eq!(red(0x41), "[41]");
+                assert_eq!(gray(0x41), "~41~");
+                assert_eq!(padding(0x41), "{41}");
+                assert_eq!(plain(0x41), " 41 ");
+            });
+
+            let colored = Theme { ansi: Some(true), ..Theme::DEFAULT };
+            with_theme(colored, || {
+                assert_eq!(red(0x41), " \x1b[91m41\x1b[0m ");
+                assert_eq!(plain(0x41), " 41 ");
+            });
+        }
+
+        #[test]
+        fn the_colorblind_palette_avoids_red_and_green_and_their_markers() {
+            let scheme = ColorScheme::colorblind();
+            assert_eq!((scheme.changed.ansi, scheme.watched.ansi), ("33", "94"));
+            assert_eq!((scheme.changed.sample(), scheme.watched.sample()), ("[xx]".to_string(), "<xx>".to_string()));
+            let markers = [scheme.changed, scheme.watched, scheme.corrupted, scheme.padding].map(|style| style.markers);
+            assert!((1..markers.len()).all(|i| !markers[..i].contains(&markers[i])));
+        }
+
+        #[test]
+        fn with_theme_restores_after_panic() {
+            let result = std::panic::catch_unwind(|| with_theme(ANGLE, || panic!("boom")));
+            assert!(result.is_err());
+            assert_ne!(theme(), ANGLE);
+        }
+    }
+}
+
+/// Call `$f::<B, N>(args)` for the runtime buffer size `$size`, where `N` is
+/// `size_of::<SizedFrame<B>>()`.
+///
+/// `MemoryView` needs the frame size as a const generic, so each supported
+/// buffer size is its own instantiation. The list must cover
+/// `1..=MAX_BUFFER_SIZE`; `Options::parse` rejects anything else.
+macro_rules! with_buffer_size {
+    ($size:expr, $f:ident $args:tt) => {
+        with_buffer_size!(@sizes $size, $f $args; 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16)
+    };
+    (@sizes $size:expr, $f:ident $args:tt; $($b:literal)*) => {
+        match $size {
+            $($b => $f::<$b, { size_of::<SizedFrame<$b>>() }> $args,)*
+            other => panic!("buffer size {other} is outside 1..={MAX_BUFFER_SIZE}"),
+        }
+    };
+}
+
+pub mod cli;
+mod csv;
+pub mod demo;
+pub mod explain;
+mod frames;
+mod history;
+mod html;
+mod html_anim;
+pub mod invariants;
+mod json;
+mod jsonl;
+pub mod layout;
+pub mod output;
+mod quiz;
+pub mod report;
+mod rng;
+pub mod runner;
+mod safe_frame;
+pub mod simulate;
+pub mod scenarios;
+mod svg;
+mod tikz;
+mod trace;
+pub mod victim;
+
+use std::cell::UnsafeCell;
+use std::mem::{offset_of, size_of};
+use std::ops::Range;
+
+pub use runner::{record_scenario, run_scenario, Config, Mode, RecordedTest, Scenario};
+
+// ============================================================================
+// THE FRAME STRUCT - Our "victim" data structure
+// ============================================================================
+
+/// Size of `Frame`'s buffer unless `--buffer-size` picks another
+pub const BUFFER_SIZE: usize = 5;
+
+/// Largest `--buffer-size` the demo is compiled for
+pub const MAX_BUFFER_SIZE: usize = 16;
+
+/// A contiguous memory region with a known, stable layout.
+///
+/// # Why `#[repr(C)]`?
+///
+/// Rust's default struct layout is unspecified - the compiler can reorder fields
+/// for efficiency. `#[repr(C)]` forces C-compatible layout: fields appear in
+/// declaration order with predictable padding. This lets us know exactly where
+/// each field lives in memory.
+///
+/// # Why `UnsafeCell`?
+///
+/// We're going to modify `len`, `num`, and `guard` through raw pointers while
+/// also reading them through `&self`. Without `UnsafeCell`, this would be
+/// undefined behavior (violating Rust's aliasing rules). `UnsafeCell` tells
+/// the compiler "this data may be mutated through shared references."
+///
+/// # The Invariant
+///
+/// Safe code assumes: `len <= B` (so `buffer[..len]` is valid)
+/// Unsafe code will violate this by overwriting `len` with garbage.
+///
+/// # Why generic?
+///
+/// `B` is the buffer size, so `--buffer-size` can show how the padding and
+/// the distance to `len` change with it. Each `B` is its own `repr(C)` struct
+/// with its own fixed layout; nothing about the layout is decided at runtime.
+/// Almost everything uses [`Frame`], the `B = BUFFER_SIZE` version.
+#[repr(C)]
+pub struct SizedFrame<const B: usize> {
+    /// The buffer we're "supposed" to write into
+    buffer: [u8; B],
+
+    /// Length field - safe code trusts this to be <= B
+    len: UnsafeCell<u32>,
+
+    /// Another value safe code might depend on
+    num: UnsafeCell<i32>,
+
+    /// Sentinel value (0xDEAD_BEEF unless `with_guard` picks another) - makes corruption visually obvious
+    guard: UnsafeCell<u32>,
+}
+
+/// The frame every mode uses, with the default buffer size
+pub type Frame = SizedFrame<BUFFER_SIZE>;
+
+/// `Frame` with `repr(packed)` added: same fields, no padding.
+///
+/// Never constructed; it exists so `--sizes` can show what packing does to the
+/// layout. Packed fields can be misaligned, so even *reading* them through a
+/// reference is UB, which is one more reason the demo sticks with `repr(C)`.
+#[allow(dead_code)] // only inspected through offset_of!/size_of
+#[repr(C, packed)]
+struct PackedFrame {
+    buffer: [u8; BUFFER_SIZE],
+    len: UnsafeCell<u32>,
+    num: UnsafeCell<i32>,
+    guard: UnsafeCell<u32>,
+}
+
+/// `Frame` without `repr(C)`: the compiler may order the fields however it likes.
+///
+/// Never constructed; `--sizes` and `--scenario repr-rust` show it next to
+/// `Frame` so the reordering is visible. In practice rustc moves the `u32`s
+/// ahead of the byte buffer, which would put `len` *before* `buffer` and make
+/// the whole overflow demo miss it.
+#[allow(dead_code)] // only inspected through offset_of!/size_of
+struct ReorderFrame {
+    buffer: [u8; BUFFER_SIZE],
+    len: UnsafeCell<u32>,
+    num: UnsafeCell<i32>,
+    guard: UnsafeCell<u32>,
+}
+
+impl<const B: usize> SizedFrame<B> {
+    /// Create a new Frame with valid initial state
+    pub fn new() -> Self {
+        Self::with_guard(GUARD_SENTINEL)
+    }
+
+    /// Create a new Frame whose guard holds `guard` instead of `GUARD_SENTINEL`.
+    ///
+    /// For matching an example that uses its own canary (`0xCAFEBABE`, say).
+    /// Whatever checks the guard afterwards has to compare against the same
+    /// value: see `check_invariant_with_guard`.
+    pub fn with_guard(guard: u32) -> Self {
+        Self {
+            buffer: [0u8; B],
+            len: UnsafeCell::new(B as u32), // Valid: len == buffer.len()
+            num: UnsafeCell::new(40_000),
+            guard: UnsafeCell::new(guard), // Easy to spot if corrupted
+        }
+    }
+
+    /// Byte offset of `buffer` within the struct (always 0 under `repr(C)`)
+    pub const fn buffer_offset() -> usize {
+        offset_of!(Self, buffer)
+    }
+
+    /// Byte offset of `len`: the first byte an overflow of `buffer` reaches
+    pub const fn len_offset() -> usize {
+        offset_of!(Self, len)
+    }
+
+    /// Byte offset of `num`
+    pub const fn num_offset() -> usize {
+        offset_of!(Self, num)
+    }
+
+    /// Byte offset of the `guard` sentinel
+    pub const fn guard_offset() -> usize {
+        offset_of!(Self, guard)
+    }
+
+    /// Size of `buffer` in bytes: the only bytes a write may legally touch
+    pub const fn buffer_size() -> usize {
+        B
+    }
+
+    /// Size of `len` in bytes
+    pub const fn len_size() -> usize {
+        size_of::<u32>()
+    }
+
+    /// Size of `num` in bytes
+    pub const fn num_size() -> usize {
+        size_of::<i32>()
+    }
+
+    /// Size of `guard` in bytes
+    pub const fn guard_size() -> usize {
+        size_of::<u32>()
+    }
+
+    /// Size of the whole struct, padding included
+    pub const fn frame_size() -> usize {
+        size_of::<Self>()
+    }
+
+    /// Create a Frame with random `buffer` and `num` (and optionally `guard`).
+    ///
+    /// `len` stays valid: the point is to vary what the dump looks like, not
+    /// to start out corrupted. The guard keeps the recognizable `guard`
+    /// unless `random_guard` is set.
+    fn randomized(rng: &mut rng::XorShift64, guard: u32, random_guard: bool) -> Self {
+        let frame = Self::with_guard(guard);
+        let mut buffer = [0u8; B];
+        buffer.iter_mut().for_each(|b| *b = rng.next_u8());
+
+        let guard = if random_guard { rng.next_u32() } else { frame.read_guard_volatile() };
+
+        Self {
+            buffer,
+            num: UnsafeCell::new(rng.next_u32() as i32),
+            guard: UnsafeCell::new(guard),
+            ..frame
+        }
+    }
+
+    /// Read `len` using volatile to prevent compiler optimizations.
+    ///
+    /// # Why volatile?
+    ///
+    /// The compiler might "know" that len was set to 5 and optimize away
+    /// the read. Volatile forces an actual memory read, so we see the
+    /// corrupted value after our unsafe writes.
+    #[inline(always)]
+    pub fn read_len_volatile(&self) -> u32 {
+        unsafe { std::ptr::read_volatile(self.len.get()) }
+    }
+
+    #[inline(always)]
+    pub fn read_num_volatile(&self) -> i32 {
+        unsafe { std::ptr::read_volatile(self.num.get()) }
+    }
+
+    #[inline(always)]
+    pub fn read_guard_volatile(&self) -> u32 {
+        unsafe { std::ptr::read_volatile(self.guard.get()) }
+    }
+
+    /// The frame's invariant as one predicate: `len <= B` and the guard still
+    /// holds `GUARD_SENTINEL`.
+    ///
+    /// This is the check a defensive API would `debug_assert!` on entry.
+    /// `safe_sum_prefix` relies on the first half without checking it; the
+    /// second half is how an overflow that reached past `len` gives itself
+    /// away. The `Err` names every part that's broken.
+    pub fn check_invariant(&self) -> Result<(), String> {
+        self.check_invariant_with_guard(GUARD_SENTINEL)
+    }
+
+    /// `check_invariant` for a frame made by `with_guard(expected)`.
+    pub fn check_invariant_with_guard(&self, expected: u32) -> Result<(), String> {
+        let (len, guard) = (self.read_len_volatile(), self.read_guard_volatile());
+        let mut broken = Vec::new();
+        if len as usize > B {
+            broken.push(format!("len = {len} > {B}"));
+        }
+        if guard != expected {
+            broken.push(format!("guard = 0x{guard:08X}, not 0x{expected:08X}"));
+        }
+        if broken.is_empty() {
+            Ok(())
+        } else {
+            Err(broken.join("; "))
+        }
+    }
+
+    /// Read `len` with an ordinary load, no volatile.
+    ///
+    /// Sound (the `UnsafeCell` allows it), but the optimizer may reuse an
+    /// earlier value if it can prove nothing wrote to `len` in between. Only
+    /// `--scenario volatile-vs-plain` and `--no-volatile` use it, for comparison.
+    #[inline(always)]
+    pub fn read_len_plain(&self) -> u32 {
+        unsafe { *self.len.get() }
+    }
+
+    #[inline(always)]
+    pub fn read_num_plain(&self) -> i32 {
+        unsafe { *self.num.get() }
+    }
+
+    #[inline(always)]
+    pub fn read_guard_plain(&self) -> u32 {
+        unsafe { *self.guard.get() }
+    }
+
+    // Out-of-line copies of the readers above, for `--explain-inline`: same
+    // volatile read, but always a real call, so the two can be compared.
+
+    #[inline(never)]
+    fn read_len_volatile_outlined(&self) -> u32 {
+        unsafe { std::ptr::read_volatile(self.len.get()) }
+    }
+
+    #[inline(never)]
+    fn read_num_volatile_outlined(&self) -> i32 {
+        unsafe { std::ptr::read_volatile(self.num.get()) }
+    }
+
+    #[inline(never)]
+    fn read_guard_volatile_outlined(&self) -> u32 {
+        unsafe { std::ptr::read_volatile(self.guard.get()) }
+    }
+
+    /// Overwrite `len` through its `UnsafeCell`, the way a parser would store
+    /// a length it read from outside the program.
+    ///
+    /// Nothing here checks the value against `BUFFER_SIZE`; that's the point.
+    #[inline(always)]
+    fn write_len_volatile(&self, len: u32) {
+        unsafe { std::ptr::write_volatile(self.len.get(), len) }
+    }
+}
+
+impl<const B: usize> Default for SizedFrame<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// FRAME LAYOUT - computed at compile time
+// ============================================================================
+
+// The byte offset of each field within Frame, from the const fns above.
+// This is stable because we used #[repr(C)].
+pub const BUF_OFF: usize = Frame::buffer_offset();
+pub const LEN_OFF: usize = Frame::len_offset();
+pub const NUM_OFF: usize = Frame::num_offset();
+pub const GUARD_OFF: usize = Frame::guard_offset();
+
+const LEN_SZ: usize = Frame::len_size();
+
+// ----------------------------------------------------------------------------
+// Memory view configuration for Frame
+// ----------------------------------------------------------------------------
+
+impl<const B: usize> SizedFrame<B> {
+    /// The byte ranges we want to highlight (the "important" fields)
+    pub const WATCHED: &'static [(usize, usize)] = &[
+        (Self::len_offset(), Self::len_offset() + Self::len_size()),       // len field
+        (Self::num_offset(), Self::num_offset() + Self::num_size()),       // num field
+        (Self::guard_offset(), Self::guard_offset() + Self::guard_size()), // guard field
+    ];
+
+    /// Bytes `repr(C)` inserted to align `len`: empty when `B` is a multiple of 4
+    pub const PADDING: &'static [(usize, usize)] = &[(Self::buffer_offset() + B, Self::len_offset())];
+}
+
+const WATCHED: &[(usize, usize)] = Frame::WATCHED;
+
+/// What `guard` holds in a fresh Frame; anything else means the canary died
+pub const GUARD_SENTINEL: u32 = 0xDEAD_BEEF;
+
+/// Total size of `Frame` in bytes, padding included
+pub const FRAME_SIZE: usize = Frame::frame_size();
+
+// ============================================================================
+// MEMORY VIEW - Visualization of memory changes
+// ============================================================================
+
+/// How a single byte should be highlighted in a rendered snapshot.
+///
+/// This is the one place that decides a byte's category; the text dump and
+/// the image frames both render from it so they can never disagree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteKind {
+    /// Changed during this iteration
+    Changed,
+    /// Inside a watched range and never corrupted
+    Watched,
+    /// Changed in an earlier iteration, but not this one
+    Corrupted,
+    /// Part of the view's `sentinel`, which no longer holds its expected value
+    Tripped,
+    /// Never changed and not watched
+    Plain,
+    /// Being read by safe code right now (`--highlight-reads`)
+    Read,
+    /// `--field-colors`: belongs to the layout's field with this index
+    Field(usize),
+    /// Not part of any field (`--field-colors`, or a view's `padding_ranges`)
+    Padding,
+}
+
+/// Which of a word's byte states wins in `--view words`: lower is more urgent.
+fn urgency(kind: ByteKind) -> u8 {
+    match kind {
+        ByteKind::Read => 0,
+        ByteKind::Changed => 1,
+        ByteKind::Tripped => 2,
+        ByteKind::Corrupted => 3,
+        ByteKind::Watched => 4,
+        ByteKind::Field(_) => 5,
+        ByteKind::Plain => 6,
+        ByteKind::Padding => 7,
+    }
+}
+
+/// One printed state of a `MemoryView`, as kept in its [`history`](MemoryView::history).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot<const N: usize> {
+    /// Row label the state was printed under (`init`, `i=3`, `rd[2]`, ...)
+    pub label: String,
+
+    pub bytes: [u8; N],
+
+    /// Offsets that differ from the previous printed state
+    pub changed: Vec<usize>,
+
+    /// How each byte was highlighted in the printed row
+    pub kinds: [ByteKind; N],
+}
+
+/// How much of a view the writes reached, from [`MemoryView::corruption_stats`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorruptionStats {
+    /// Bytes that changed at least once, buffer included
+    pub corrupted_bytes: usize,
+
+    /// How many of those fall inside a watched range
+    pub watched_bytes_hit: usize,
+
+    /// One entry per watched range, in the order the view lists them
+    pub fields: Vec<FieldDamage>,
+}
+
+/// How many bytes of one watched range have changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDamage {
+    /// The field the range belongs to, when the view knows its layout
+    pub name: Option<&'static str>,
+
+    /// The watched byte range, `start..end`
+    pub range: (usize, usize),
+
+    /// Bytes in the range that changed at least once
+    pub bytes_hit: usize,
+}
+
+impl FieldDamage {
+    pub fn size(&self) -> usize {
+        self.range.1 - self.range.0
+    }
+
+    /// Every byte of the field changed
+    pub fn is_fully_corrupted(&self) -> bool {
+        self.bytes_hit == self.size()
+    }
+
+    /// Some, but not all, of the field's bytes changed
+    pub fn is_partially_corrupted(&self) -> bool {
+        self.bytes_hit > 0 && !self.is_fully_corrupted()
+    }
+
+    /// The field's name, or its byte range if the view doesn't know it
+    pub fn label(&self) -> String {
+        match self.name {
+            Some(name) => name.to_string(),
+            None => format!("[{}..{})", self.range.0, self.range.1),
+        }
+    }
+}
+
+impl CorruptionStats {
+    /// Watched fields with every byte changed
+    pub fn fully_corrupted(&self) -> impl Iterator<Item = &FieldDamage> {
+        self.fields.iter().filter(|f| f.is_fully_corrupted())
+    }
+
+    /// Watched fields with some, but not all, bytes changed
+    pub fn partially_corrupted(&self) -> impl Iterator<Item = &FieldDamage> {
+        self.fields.iter().filter(|f| f.is_partially_corrupted())
+    }
+}
+
+/// Tracks memory snapshots and highlights changes between iterations.
+///
+/// Generic over `N` (the size of the memory region to track).
+pub struct MemoryView<const N: usize> {
+    /// Current snapshot of memory
+    pub snapshot: [u8; N],
+
+    /// Which bytes have been corrupted (changed at least once)
+    corrupted: [bool; N],
+
+    /// Byte ranges to highlight as "watched" (e.g., the len/num/guard fields)
+    watched_ranges: &'static [(usize, usize)],
+
+    /// Byte positions where we print a "|" separator for readability
+    separators: Vec<usize>,
+
+    /// Annotate each changed byte with its before/after bit pattern
+    pub show_bits: bool,
+
+    /// Append an `xxd`-style column of the printable-ASCII bytes to each row
+    pub ascii: bool,
+
+    /// Start each row with the offset of its first byte (`0x0000`), like a debugger
+    pub show_offsets: bool,
+
+    /// Wrap rows after this many bytes; the default (`usize::MAX`) never wraps
+    pub bytes_per_row: usize,
+
+    /// Byte ranges no field owns, drawn in the padding style (`{xx}`) unless
+    /// they changed this row
+    pub padding_ranges: &'static [(usize, usize)],
+
+    /// A little-endian `u32` canary: its offset and the value it should hold.
+    /// Once the snapshot disagrees, all four bytes are drawn in the tripped
+    /// style (`!xx!`) unless they changed this row
+    pub sentinel: Option<(usize, u32)>,
+
+    /// When set, tint every byte by its owning field instead of by change state
+    field_layout: Option<&'static layout::Layout>,
+
+    /// Offset that safe code is reading right now, highlighted above all else
+    reading: Option<usize>,
+
+    /// Names the watched ranges in `corruption_stats`; set by `for_victim`
+    field_names: Option<&'static layout::Layout>,
+
+    /// Every printed state, in order, since the view was made or last cleared
+    history: Vec<Snapshot<N>>,
+
+    /// Callbacks to run when the byte at an offset changes, like a debugger's
+    /// hardware watchpoint
+    watchpoints: Vec<(usize, Watchpoint)>,
+
+    /// Callbacks to run the first time each watched range changes, from `print_write`
+    field_alarms: Vec<FieldAlarm>,
+
+    /// Draw with these styles instead of the current theme's scheme
+    pub color_scheme: Option<color::ColorScheme>,
+
+    /// Draw bytes, or little-endian `u32` words
+    pub view_mode: ViewMode,
+}
+
+/// Called with a watched byte's `(old, new)` values when it changes.
+pub type Watchpoint = Box<dyn FnMut(u8, u8)>;
+
+/// Called with a watched field's name and the write index, the first time
+/// any of the field's bytes changes.
+pub type FieldAlarm = Box<dyn FnMut(&str, usize)>;
+
+/// Sets up a [`MemoryView`] one option at a time.
+///
+/// ```
+/// use rust_uaf::{MemoryView, MemoryViewBuilder};
+///
+/// let view: MemoryView<8> = MemoryViewBuilder::new()
+///     .watched_ranges(&[(4, 8)])
+///     .separators(&[4])
+///     .ascii(true)
+///     .bytes_per_row(4)
+///     .build();
+/// assert!(view.is_watched(5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MemoryViewBuilder {
+    watched_ranges: &'static [(usize, usize)],
+    separators: Vec<usize>,
+    padding_ranges: &'static [(usize, usize)],
+    show_bits: bool,
+    ascii: bool,
+    show_offsets: bool,
+    bytes_per_row: usize,
+    color_scheme: Option<color::ColorScheme>,
+    view_mode: ViewMode,
+}
+
+impl Default for MemoryViewBuilder {
+    fn default() -> Self {
+        Self {
+            watched_ranges: &[],
+            separators: Vec::new(),
+            padding_ranges: &[],
+            show_bits: false,
+            ascii: false,
+            show_offsets: false,
+            bytes_per_row: usize::MAX,
+            color_scheme: None,
+            view_mode: ViewMode::Bytes,
+        }
+    }
+}
+
+impl MemoryViewBuilder {
+    /// Nothing watched, no separators, one line per snapshot, the theme's colors
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Byte ranges to highlight until they're corrupted
+    pub fn watched_ranges(mut self, ranges: &'static [(usize, usize)]) -> Self {
+        self.watched_ranges = ranges;
+        self
+    }
+
+    /// Offsets to draw a `|` before
+    pub fn separators(mut self, separators: &[usize]) -> Self {
+        self.separators = separators.to_vec();
+        self
+    }
+
+    /// Byte ranges no field owns, drawn in the padding style
+    pub fn padding_ranges(mut self, ranges: &'static [(usize, usize)]) -> Self {
+        self.padding_ranges = ranges;
+        self
+    }
+
+    /// Annotate each changed byte with its before/after bits
+    pub fn show_bits(mut self, on: bool) -> Self {
+        self.show_bits = on;
+        self
+    }
+
+    /// Add an `xxd`-style ASCII column
+    pub fn ascii(mut self, on: bool) -> Self {
+        self.ascii = on;
+        self
+    }
+
+    /// Start each row with the offset of its first byte
+    pub fn show_offsets(mut self, on: bool) -> Self {
+        self.show_offsets = on;
+        self
+    }
+
+    /// Wrap rows after this many bytes
+    pub fn bytes_per_row(mut self, bytes: usize) -> Self {
+        self.bytes_per_row = bytes;
+        self
+    }
+
+    /// Draw with `scheme` instead of whatever the current theme uses
+    pub fn color_scheme(mut self, scheme: color::ColorScheme) -> Self {
+        self.color_scheme = Some(scheme);
+        self
+    }
+
+    /// Draw little-endian `u32` words instead of bytes
+    pub fn view_mode(mut self, mode: ViewMode) -> Self {
+        self.view_mode = mode;
+        self
+    }
+
+    /// A view of `N` bytes with everything set so far
+    pub fn build<const N: usize>(self) -> MemoryView<N> {
+        MemoryView {
+            snapshot: [0u8; N],
+            corrupted: [false; N],
+            watched_ranges: self.watched_ranges,
+            separators: self.separators,
+            show_bits: self.show_bits,
+            ascii: self.ascii,
+            show_offsets: self.show_offsets,
+            bytes_per_row: self.bytes_per_row,
+            padding_ranges: self.padding_ranges,
+            sentinel: None,
+            field_layout: None,
+            reading: None,
+            field_names: None,
+            history: Vec::new(),
+            watchpoints: Vec::new(),
+            field_alarms: Vec::new(),
+            color_scheme: self.color_scheme,
+            view_mode: self.view_mode,
+        }
+    }
+}
+
+/// `offsets` as separators for an `size`-byte dump: sorted, each drawn once,
+/// and none at either edge, where a `|` would separate nothing.
+pub(crate) fn field_boundaries(offsets: impl IntoIterator<Item = usize>, size: usize) -> Vec<usize> {
+    let mut separators: Vec<usize> = offsets.into_iter().filter(|&i| i > 0 && i < size).collect();
+    separators.sort_unstable();
+    separators.dedup();
+    separators
+}
+
+impl<const N: usize> MemoryView<N> {
+    /// A view with the given watched ranges and separators and every other
+    /// option at its default; [`MemoryViewBuilder`] sets the rest.
+    pub fn new(watched_ranges: &'static [(usize, usize)], separators: &'static [usize]) -> Self {
+        MemoryViewBuilder::new().watched_ranges(watched_ranges).separators(separators).build()
+    }
+
+    /// A view watching `ranges`, with a separator before each range instead of
+    /// a hand-kept list. [`for_victim`](MemoryView::for_victim) also separates
+    /// the buffer from any padding that follows it.
+    pub fn with_field_boundaries(ranges: &'static [(usize, usize)]) -> Self {
+        let mut view = Self::new(ranges, &[]);
+        view.separators = field_boundaries(ranges.iter().map(|&(start, _)| start), N);
+        view
+    }
+
+    /// Apply the dump options every view takes from the command line:
+    /// `--bits`, `--ascii`, `--offsets`, `--bytes-per-row` and `--view`.
+    pub fn apply_display_options(&mut self, opts: &cli::Options) {
+        self.show_bits = opts.bits;
+        self.ascii = opts.ascii;
+        self.show_offsets = opts.offsets;
+        self.bytes_per_row = opts.bytes_per_row;
+        self.view_mode = opts.view;
+    }
+
+    /// Call `on_change(old, new)` whenever a diff shows byte `offset` changing.
+    pub fn add_watchpoint(&mut self, offset: usize, on_change: Watchpoint) {
+        assert!(offset < N, "watchpoint at {offset} is outside the {N}-byte view");
+        self.watchpoints.push((offset, on_change));
+    }
+
+    /// Run the callbacks for every watched offset that differs from `prev`.
+    fn fire_watchpoints(&mut self, prev: &[u8; N]) {
+        for (offset, on_change) in &mut self.watchpoints {
+            let (old, new) = (prev[*offset], self.snapshot[*offset]);
+            if old != new {
+                on_change(old, new);
+            }
+        }
+    }
+
+    /// Call `on_corrupted(field, index)` when a `print_write` shows a watched
+    /// range changing for the first time since the view was made or cleared.
+    ///
+    /// Fields are named from the victim's layout (see `for_victim`); a view
+    /// without one names them by byte range, like `[8..12)`.
+    pub fn add_field_alarm(&mut self, on_corrupted: FieldAlarm) {
+        self.field_alarms.push(on_corrupted);
+    }
+
+    /// The layout's name for the field starting at `start`, if the view has a layout
+    fn watched_name(&self, start: usize) -> Option<&'static str> {
+        let layout = self.field_names?;
+        layout.fields.iter().find(|f| f.offset == start).map(|f| f.name)
+    }
+
+    /// Every state printed by `print_row`/`print_diff` (or rendered by
+    /// `render_diff`), oldest first, with the offsets each one changed.
+    ///
+    /// A `capture` on its own only refreshes `snapshot`: a state joins the
+    /// timeline when it is shown, so capture-then-print is one entry.
+    pub fn history(&self) -> &[Snapshot<N>] {
+        &self.history
+    }
+
+    /// Start a fresh timeline, e.g. when reusing the view for another test.
+    ///
+    /// Also forgets which bytes were corrupted, so the next test's watched
+    /// fields are highlighted as intact again.
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+        self.corrupted = [false; N];
+    }
+
+    /// Remember the current snapshot under `label`.
+    fn record(&mut self, label: &str, changed: Vec<usize>) {
+        // Bytes changed this row classify as Changed whatever their corrupted
+        // flag says, so classifying after print_diff updated it is still exact
+        let kinds = std::array::from_fn(|i| self.classify(i, changed.contains(&i)));
+        self.history.push(Snapshot {
+            label: label.to_string(),
+            bytes: self.snapshot,
+            changed,
+            kinds,
+        });
+    }
+
+    /// Tally the bytes changed since the view was made (or last cleared),
+    /// overall and per watched range.
+    pub fn corruption_stats(&self) -> CorruptionStats {
+        let hit = |start: usize, end: usize| (start..end.min(N)).filter(|&i| self.corrupted[i]).count();
+        let fields: Vec<FieldDamage> = self
+            .watched_ranges
+            .iter()
+            .map(|&(start, end)| FieldDamage {
+                name: self.watched_name(start),
+                range: (start, end),
+                bytes_hit: hit(start, end),
+            })
+            .collect();
+
+        CorruptionStats {
+            corrupted_bytes: hit(0, N),
+            watched_bytes_hit: (0..N).filter(|&i| self.corrupted[i] && self.is_watched(i)).count(),
+            fields,
+        }
+    }
+
+    /// Copy N bytes from memory into our snapshot
+    fn capture(&mut self, base_ptr: *const u8) {
+        unsafe {
+            std::ptr::copy_nonoverlapping(base_ptr, self.snapshot.as_mut_ptr(), N);
+        }
+    }
+
+    /// Reconstruct a little-endian `u32` field from the snapshot bytes.
+    ///
+    /// This reads what the dump *shows*, not the live struct, so it always
+    /// agrees with the row that was just printed.
+    pub fn read_u32(&self, offset: usize) -> u32 {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(&self.snapshot[offset..offset + 4]);
+        u32::from_le_bytes(bytes)
+    }
+
+    /// Reconstruct a little-endian `i32` field from the snapshot bytes.
+    ///
+    /// Same bytes as `read_u32`, read as two's complement: `ff ff ff ff` is `-1`.
+    pub fn read_i32(&self, offset: usize) -> i32 {
+        self.read_u32(offset) as i32
+    }
+
+    /// Should we print a separator before this byte index?
+    fn is_separator(&self, i: usize) -> bool {
+        self.separators.contains(&i)
+    }
+
+    /// Is this byte in one of the watched ranges?
+    pub fn is_watched(&self, i: usize) -> bool {
+        self.watched_ranges
+            .iter()
+            .any(|&(start, end)| i >= start && i < end)
+    }
+
+    /// Is this byte padding, according to `padding_ranges`?
+    fn is_padding(&self, i: usize) -> bool {
+        self.padding_ranges.iter().any(|&(start, end)| i >= start && i < end)
+    }
+
+    /// Is this byte part of a `sentinel` that no longer holds its value?
+    fn is_tripped(&self, i: usize) -> bool {
+        self.sentinel
+            .is_some_and(|(offset, expected)| (offset..offset + 4).contains(&i) && self.read_u32(offset) != expected)
+    }
+
+    /// Decide how byte `i` should be highlighted
+    fn classify(&self, i: usize, changed_this_iter: bool) -> ByteKind {
+        if self.reading == Some(i) {
+            return ByteKind::Read;
+        }
+
+        if let Some(layout) = self.field_layout {
+            // Structural view: ownership only, change state is ignored
+            return match layout.field_index_at(i) {
+                Some(index) => ByteKind::Field(index),
+                None => ByteKind::Padding,
+            };
+        }
+
+        if changed_this_iter {
+            ByteKind::Changed // Just changed - highlight in red
+        } else if self.is_tripped(i) {
+            ByteKind::Tripped // The canary is dead - yellow
+        } else if self.corrupted[i] {
+            ByteKind::Corrupted // Changed earlier - gray
+        } else if self.is_padding(i) {
+            ByteKind::Padding // Belongs to no field - dim
+        } else if self.is_watched(i) {
+            ByteKind::Watched // Watched and pristine - highlight in green
+        } else {
+            ByteKind::Plain
+        }
+    }
+
+    /// Does a watched or padding range begin exactly at byte `i`?
+    fn starts_marked_range(&self, i: usize) -> bool {
+        self.watched_ranges
+            .iter()
+            .chain(self.padding_ranges)
+            .any(|&(start, end)| start == i && start < end)
+    }
+
+    /// Format a single byte, including any separator that precedes it
+    fn format_byte(&self, scheme: &color::ColorScheme, i: usize, byte: u8, changed_this_iter: bool) -> String {
+        let mut out = String::new();
+        if self.is_separator(i) {
+            out.push_str(" |");
+
+            // Without this, `|` runs straight into a marker like `(05)` and
+            // reads as part of it. Keyed on position (not on the byte's
+            // current state) so every row keeps the same column widths.
+            if self.starts_marked_range(i) {
+                out.push(' ');
+            }
+        }
+
+        let formatted = match self.classify(i, changed_this_iter) {
+            ByteKind::Changed => color::paint(scheme.changed, byte),
+            ByteKind::Watched => color::paint(scheme.watched, byte),
+            ByteKind::Read => color::read(byte),
+            ByteKind::Corrupted => color::paint(scheme.corrupted, byte),
+            ByteKind::Tripped => color::tripped(byte),
+            ByteKind::Plain => color::paint(scheme.plain, byte),
+            ByteKind::Field(index) => color::field(index, byte),
+            ByteKind::Padding => color::paint(scheme.padding, byte),
+        };
+        out.push_str(&formatted);
+        out
+    }
+
+    /// The current snapshot as one labelled row (no diff highlighting).
+    ///
+    /// Includes the ANSI codes or markers exactly as printed, but not the
+    /// trailing newline.
+    pub fn render_row(&self, label: &str) -> String {
+        self.render_lines(label, |_| false)
+    }
+
+    /// The snapshot as one or more physical lines of `bytes_per_row` bytes.
+    ///
+    /// Only the first line carries `label`; later ones leave it blank so the
+    /// bytes line up. Each line gets its own gutter and ASCII column, and
+    /// separators and highlighting come from `format_byte` as usual, so they
+    /// don't care where a line breaks.
+    fn render_lines(&self, label: &str, changed_this_iter: impl Fn(usize) -> bool) -> String {
+        // A view's own scheme applies to everything drawn below, ASCII column included
+        match self.color_scheme {
+            Some(scheme) => {
+                let theme = color::Theme { scheme, ..color::theme() };
+                color::with_theme(theme, || self.render_lines_in_theme(label, changed_this_iter))
+            }
+            None => self.render_lines_in_theme(label, changed_this_iter),
+        }
+    }
+
+    /// `render_lines` in whatever theme is current
+    fn render_lines_in_theme(&self, label: &str, changed_this_iter: impl Fn(usize) -> bool) -> String {
+        if self.view_mode == ViewMode::Words {
+            return self.render_words(label, changed_this_iter);
+        }
+        let per_row = self.bytes_per_row.max(1);
+        let scheme = color::theme().scheme;
+        let mut text = String::new();
+        for start in (0..N).step_by(per_row) {
+            let end = N.min(start.saturating_add(per_row));
+            if start > 0 {
+                text.push('\n');
+            }
+            let label = if start == 0 { label } else { "" };
+            text.push_str(&format!("{}{label:<6} |", self.gutter(start)));
+            for i in start..end {
+                let cell = self.format_byte(&scheme, i, self.snapshot[i], changed_this_iter(i));
+                // A separator right after the row's own `|` would just double it
+                let cell = if i == start { cell.strip_prefix(" |").unwrap_or(&cell) } else { &cell };
+                text.push_str(cell);
+            }
+            if self.ascii {
+                // Keep a short last line's ASCII column under the ones above it
+                if start > 0 && end - start < per_row {
+                    text.push_str(&" ".repeat(4 * (per_row - (end - start))));
+                }
+                text.push_str(&self.render_ascii(start..end, &changed_this_iter));
+            }
+        }
+        text
+    }
+
+    /// `render_lines_in_theme` for `ViewMode::Words`.
+    ///
+    /// Each word is highlighted by the most urgent state of its bytes, so a
+    /// word with one freshly overwritten byte shows as changed. Separators
+    /// are drawn only where one falls on a word boundary, and a line holds
+    /// `bytes_per_row / 4` words (at least one).
+    fn render_words(&self, label: &str, changed_this_iter: impl Fn(usize) -> bool) -> String {
+        let per_row = (self.bytes_per_row / 4).max(1).saturating_mul(4);
+        let mut text = String::new();
+        for start in (0..N).step_by(per_row) {
+            let end = N.min(start.saturating_add(per_row));
+            if start > 0 {
+                text.push('\n');
+            }
+            let label = if start == 0 { label } else { "" };
+            text.push_str(&format!("{}{label:<6} |", self.gutter(start)));
+            for word in (start..end).step_by(4) {
+                let bytes = word..end.min(word + 4);
+                if word > start && self.is_separator(word) {
+                    text.push_str(" |");
+                }
+                let kinds = bytes.clone().map(|i| self.classify(i, changed_this_iter(i)));
+                let kind = kinds.min_by_key(|&kind| urgency(kind));
+                let hex: String = bytes.clone().rev().map(|i| format!("{:02x}", self.snapshot[i])).collect();
+                let missing = "..".repeat(4 - bytes.len());
+                text.push_str(&color::cell(kind.unwrap_or(ByteKind::Plain), &format!("0x{missing}{hex}")));
+            }
+            if self.ascii {
+                if start > 0 && end - start < per_row {
+                    text.push_str(&" ".repeat(12 * (per_row - (end - start)).div_ceil(4)));
+                }
+                text.push_str(&self.render_ascii(start..end, &changed_this_iter));
+            }
+        }
+        text
+    }
+
+    /// The address gutter for a line starting at byte `offset`, if enabled
+    fn gutter(&self, offset: usize) -> String {
+        if self.show_offsets {
+            format!("0x{offset:04x} ")
+        } else {
+            String::new()
+        }
+    }
+
+    /// The ASCII sidebar, highlighted the same way as the hex: `.` for
+    /// anything outside `0x20..=0x7e`.
+    fn render_ascii(&self, bytes: Range<usize>, changed_this_iter: impl Fn(usize) -> bool) -> String {
+        let mut column = String::from("  ");
+        for i in bytes {
+            column.push_str(&color::ascii(self.classify(i, changed_this_iter(i)), self.snapshot[i]));
+        }
+        column
+    }
+
+    /// Print current snapshot with a label (no diff highlighting)
+    pub fn print_row(&mut self, label: &str) {
+        outln!("{}", self.render_row(label));
+        self.record(label, Vec::new());
+    }
+
+    /// One line per changed byte showing exactly which bits flipped.
+    ///
+    /// A byte going from `0x05` to `0x07` is "one changed byte" in the hex
+    /// dump, but really it's bit 1 being set. This makes that visible:
+    ///
+    /// ```text
+    ///        byte 0x08: 00000101 -> 00000111  (bit 1 flipped)
+    /// ```
+    fn render_bit_changes(&self, prev: &[u8; N]) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (i, (&p, &c)) in prev.iter().zip(self.snapshot.iter()).enumerate() {
+            let flipped = p ^ c;
+            if flipped == 0 {
+                continue;
+            }
+
+            // Render MSB first, highlighting only the bits that flipped
+            let after: String = (0..8)
+                .rev()
+                .map(|bit| {
+                    let digit = if c & (1 << bit) != 0 { "1" } else { "0" };
+                    if flipped & (1 << bit) != 0 {
+                        color::changed_text(digit)
+                    } else {
+                        digit.to_string()
+                    }
+                })
+                .collect();
+
+            let bits: Vec<String> = (0..8)
+                .rev()
+                .filter(|bit| flipped & (1 << bit) != 0)
+                .map(|bit| bit.to_string())
+                .collect();
+            let noun = if bits.len() == 1 { "bit" } else { "bits" };
+
+            lines.push(format!("       byte 0x{i:02x}: {p:08b} -> {after}  ({noun} {} flipped)", bits.join(", ")));
+        }
+        lines
+    }
+
+    /// The current snapshot as a row highlighting differences from `prev`.
+    ///
+    /// With `show_bits`, the bit-change lines follow the row (newline
+    /// separated, no trailing newline). Like `print_diff`, this marks the
+    /// changed bytes as corrupted and records history; it does not fire
+    /// watchpoints, since those may print or block.
+    pub fn render_diff(&mut self, prev: &[u8; N], label: &str) -> String {
+        let mut text = self.render_lines(label, |i| prev[i] != self.snapshot[i]);
+
+        if self.show_bits {
+            for line in self.render_bit_changes(prev) {
+                text.push('\n');
+                text.push_str(&" ".repeat(self.gutter(0).len()));
+                text.push_str(&line);
+            }
+        }
+
+        // Mark any changed bytes as corrupted for future iterations
+        self.mark_changes(prev);
+
+        let changed = (0..N).filter(|&i| prev[i] != self.snapshot[i]).collect();
+        self.record(label, changed);
+        text
+    }
+
+    /// Two frames stacked, `other` first under `labels.0` and this view's
+    /// snapshot under `labels.1`, with every byte that differs between them
+    /// highlighted as changed in both rows.
+    ///
+    /// Only the two frames are compared: bytes corrupted earlier, a tripped
+    /// sentinel, or whatever `render_diff` last saw play no part. Passing the
+    /// earlier frame as `other` reads top to bottom as before, then after.
+    pub fn render_compare(&self, other: &[u8; N], labels: (&str, &str)) -> String {
+        let differs = |i: usize| other[i] != self.snapshot[i];
+        let mut view: MemoryView<N> = MemoryViewBuilder {
+            watched_ranges: self.watched_ranges,
+            separators: self.separators.clone(),
+            padding_ranges: self.padding_ranges,
+            show_bits: false,
+            ascii: self.ascii,
+            show_offsets: self.show_offsets,
+            bytes_per_row: self.bytes_per_row,
+            color_scheme: self.color_scheme,
+            view_mode: self.view_mode,
+        }
+        .build();
+
+        view.snapshot = *other;
+        let first = view.render_lines(labels.0, differs);
+        view.snapshot = self.snapshot;
+        let second = view.render_lines(labels.1, differs);
+        format!("{first}\n{second}")
+    }
+
+    /// Print `render_compare` under a line counting the bytes that differ.
+    pub fn print_compare(&self, other: &[u8; N], labels: (&str, &str)) {
+        let differing = (0..N).filter(|&i| other[i] != self.snapshot[i]).count();
+        let noun = if differing == 1 { "byte differs" } else { "bytes differ" };
+        outln!("{} vs {} ({differing} {noun}):", labels.0, labels.1);
+        outln!("{}", self.render_compare(other, labels));
+    }
+
+    /// A legend for this view's dumps, one entry per line.
+    ///
+    /// Built from what the view will actually draw: each sample is painted
+    /// with the scheme in effect, so colors, markers, and `--palette` always
+    /// match the dump. Entries only appear for states that can occur (padding
+    /// and a dead sentinel need `padding_ranges` and `sentinel`, the `reads`
+    /// entry is for `--highlight-reads`), followed by a note for each display
+    /// option that is on.
+    pub fn render_legend(&self, reads: bool) -> String {
+        match self.color_scheme {
+            Some(scheme) => {
+                let theme = color::Theme { scheme, ..color::theme() };
+                color::with_theme(theme, || self.render_legend_in_theme(reads))
+            }
+            None => self.render_legend_in_theme(reads),
+        }
+    }
+
+    /// `render_legend` in whatever theme is current
+    fn render_legend_in_theme(&self, reads: bool) -> String {
+        let words = self.view_mode == ViewMode::Words;
+        let (unit, sample) = if words { ("word", "0x________") } else { ("byte", "xx") };
+        let entry = |kind: ByteKind, meaning: &str| format!("  {} = {meaning}", color::cell(kind, sample));
+        // Unstyled symbols line up with the samples' `=`
+        let width = sample.len() + 2;
+
+        let mut lines = Vec::new();
+        if let Some(layout) = self.field_layout {
+            let fields: Vec<String> =
+                layout.fields.iter().enumerate().map(|(index, field)| color::field_label(index, field.name)).collect();
+            lines.push(format!("Legend (field colors; {unit}s are tinted by owner, not by change):"));
+            lines.push(format!("  {} {}", fields.join(" "), color::padding_label()));
+        } else {
+            lines.push("Legend:".to_string());
+            if !self.watched_ranges.is_empty() {
+                lines.push(entry(ByteKind::Watched, "watched field, not yet corrupted"));
+            }
+            lines.push(entry(ByteKind::Changed, &format!("{unit} changed this iteration")));
+            lines.push(entry(ByteKind::Corrupted, &format!("{unit} changed in an earlier iteration")));
+            if self.sentinel.is_some() {
+                lines.push(entry(ByteKind::Tripped, "guard that no longer holds its sentinel (the canary is dead)"));
+            }
+            if reads {
+                lines.push(entry(ByteKind::Read, &format!("{unit} safe code is reading right now")));
+            }
+            lines.push(entry(ByteKind::Plain, &format!("plain {unit}")));
+            if self.padding_ranges.iter().any(|(start, end)| start < end) {
+                lines.push(entry(ByteKind::Padding, "padding: belongs to no field, only there for alignment"));
+            }
+        }
+        if !self.separators.is_empty() {
+            lines.push(format!("  {:^width$} = field boundary", "|"));
+        }
+        if words {
+            lines.push("  Each word is four bytes read as a little-endian u32, styled by its most urgent byte.".into());
+        }
+        if self.show_offsets {
+            lines.push("  Each row starts with the offset of its first byte (0x0000).".to_string());
+        }
+        if self.ascii {
+            lines.push("  The column on the right repeats printable bytes as ASCII (`.` for the rest).".to_string());
+        }
+        if self.show_bits {
+            lines.push("  Each changed byte gets a line below its row showing which bits flipped.".to_string());
+        }
+        lines.join("\n")
+    }
+
+    /// Print `render_legend`.
+    pub fn print_legend(&self, reads: bool) {
+        outln!("{}", self.render_legend(reads));
+    }
+
+    /// Remember every byte that differs from `prev` as corrupted, without drawing anything.
+    fn mark_changes(&mut self, prev: &[u8; N]) {
+        for (i, corrupted) in self.corrupted.iter_mut().enumerate() {
+            if prev[i] != self.snapshot[i] {
+                *corrupted = true;
+            }
+        }
+    }
+
+    /// Print current snapshot, highlighting differences from `prev`
+    pub fn print_diff(&mut self, prev: &[u8; N], label: &str) {
+        let text = self.render_diff(prev, label);
+        outln!("{text}");
+        self.fire_watchpoints(prev);
+    }
+
+    /// `print_diff` for write `index` of the overflow, labeled `i=<index>`,
+    /// then the field alarms for every watched range this write corrupted first.
+    pub fn print_write(&mut self, prev: &[u8; N], index: usize) {
+        let ranges = self.watched_ranges;
+        let untouched: Vec<bool> = ranges
+            .iter()
+            .map(|&(start, end)| !(start..end.min(N)).any(|i| self.corrupted[i]))
+            .collect();
+        self.print_diff(prev, &format!("i={index}"));
+
+        for (&(start, end), untouched) in ranges.iter().zip(untouched) {
+            if untouched && (start..end.min(N)).any(|i| self.corrupted[i]) {
+                let name = match self.watched_name(start) {
+                    Some(name) => name.to_string(),
+                    None => format!("[{start}..{end})"),
+                };
+                for on_corrupted in &mut self.field_alarms {
+                    on_corrupted(&name, index);
+                }
+            }
+        }
+    }
+}
+
+// ============================================================================
+// SAFE CODE THAT TRUSTS THE INVARIANT
+// ============================================================================
+
+/// Sum the first `len` bytes of the buffer.
+///
+/// # The Problem
+///
+/// This function is 100% safe Rust - no `unsafe` keyword anywhere.
+/// It trusts that `frame.len` is a valid length (<= BUFFER_SIZE).
+///
+/// But if unsafe code corrupted `len` to be larger than BUFFER_SIZE,
+/// the slice `buffer[..len]` will panic with an out-of-bounds error.
+///
+/// This demonstrates: **unsafe code can break safe code's assumptions.**
+pub fn safe_sum_prefix<const B: usize>(frame: &SizedFrame<B>) -> u64 {
+    let len = frame.read_len_volatile() as usize;
+
+    // This line will PANIC if len > BUFFER_SIZE
+    // The bounds check is done by safe Rust, but it fails because
+    // unsafe code corrupted the `len` field.
+    frame.buffer[..len].iter().map(|&b| b as u64).sum()
+}
+
+/// Sum the whole buffer without consulting `len` at all.
+///
+/// `buffer.iter()` can only ever yield `BUFFER_SIZE` elements, so there is no
+/// index for the compiler to check and nothing an overwritten `len` can
+/// change. Compare `safe_sum_prefix`, whose slice bound comes from memory at
+/// runtime and must be checked. (See `--explain-elision`.)
+fn sum_fixed(frame: &Frame) -> u64 {
+    frame.buffer.iter().map(|&b| b as u64).sum()
+}
+
+/// `safe_sum_prefix`, one index at a time, reporting each index before reading it.
+///
+/// Same contract and same trust in `len`; the only difference is that the
+/// bounds check fires on `buffer[i]` for the first bad `i` instead of on the
+/// up-front slice, so a caller can watch the reads march toward it.
+#[allow(clippy::needless_range_loop)] // the explicit, bounds-checked index is the point
+fn safe_sum_prefix_traced<const B: usize>(frame: &SizedFrame<B>, mut on_read: impl FnMut(usize)) -> u64 {
+    let len = frame.read_len_volatile() as usize;
+
+    let mut sum = 0;
+    for i in 0..len {
+        on_read(i);
+        // PANICS as soon as i reaches BUFFER_SIZE
+        sum += frame.buffer[i] as u64;
+    }
+    sum
+}
+
+/// The largest of the first `len` bytes of the buffer (0 if `len` is 0).
+///
+/// Safe Rust with the same trust in `len` as `safe_sum_prefix`, and the same
+/// panic on `buffer[..len]` once an overflow has made `len` too big.
+pub fn safe_max_byte<const B: usize>(frame: &SizedFrame<B>) -> u8 {
+    let len = frame.read_len_volatile() as usize;
+
+    // PANICS if len > BUFFER_SIZE
+    frame.buffer[..len].iter().copied().max().unwrap_or(0)
+}
+
+/// Copy the first `len` bytes of the buffer out, the way a getter handing
+/// back "the valid part" would.
+///
+/// Again 100% safe, and again it's the slice bound from a corrupted `len`
+/// that panics.
+pub fn safe_copy_out<const B: usize>(frame: &SizedFrame<B>) -> Vec<u8> {
+    let len = frame.read_len_volatile() as usize;
+
+    // PANICS if len > BUFFER_SIZE
+    frame.buffer[..len].to_vec()
+}
+
+/// Which safe function the demo runs against the corrupted frame (`--consumer`).
+///
+/// They compute different things, but every one of them slices the buffer by
+/// `len`: one overwritten field breaks them all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Consumer {
+    /// `safe_sum_prefix`
+    #[default]
+    Sum,
+    /// `safe_max_byte`
+    Max,
+    /// `safe_copy_out`
+    Copy,
+}
+
+impl Consumer {
+    /// The function's name, for output like `safe_max_byte() = 4`
+    pub fn name(self) -> &'static str {
+        match self {
+            Consumer::Sum => "safe_sum_prefix",
+            Consumer::Max => "safe_max_byte",
+            Consumer::Copy => "safe_copy_out",
+        }
+    }
+
+    /// Call the function on `frame` and format what it returned.
+    ///
+    /// # Panics
+    ///
+    /// Whenever the function does: when `len` is bigger than the buffer.
+    pub fn run<const B: usize>(self, frame: &SizedFrame<B>) -> String {
+        match self {
+            Consumer::Sum => safe_sum_prefix(frame).to_string(),
+            Consumer::Max => safe_max_byte(frame).to_string(),
+            Consumer::Copy => format!("{:02x?}", safe_copy_out(frame)),
+        }
+    }
+}
+
+// ============================================================================
+// PROGRAMMATIC CORRUPTION
+// ============================================================================
+
+/// Apply an arbitrary list of `(offset, byte)` writes to `frame` and return
+/// the resulting bytes.
+///
+/// Offsets are relative to the start of the `Frame`, not the buffer, so a
+/// write at `offset_of!(Frame, len)` hits `len` regardless of padding. This is
+/// the same unchecked raw-pointer write the demo loop performs, minus the
+/// printing, which makes it a convenient single entry point for tests and for
+/// replaying a recorded corruption.
+///
+/// # Panics
+///
+/// If an offset is outside the `Frame`. Writing past the struct would leave
+/// the allocation entirely, which is UB we have no way to observe.
+pub fn apply_writes(frame: &mut Frame, writes: &[(usize, u8)]) -> [u8; FRAME_SIZE] {
+    let base_ptr: *mut u8 = (frame as *mut Frame).cast::<u8>();
+
+    for &(offset, byte) in writes {
+        assert!(
+            offset < FRAME_SIZE,
+            "write at offset {offset} is outside the {FRAME_SIZE}-byte Frame"
+        );
+        // No bounds check against the buffer: any in-struct byte is fair game
+        unsafe { *base_ptr.add(offset) = byte };
+    }
+
+    let mut view: MemoryView<FRAME_SIZE> = MemoryView::new(&[], &[]);
+    view.capture(base_ptr);
+    view.snapshot
+}
+
+/// What the demo loop writes past the buffer (`--fill`).
+///
+/// Fields are reconstructed little-endian from whatever lands in them, so a
+/// constant `0x41` makes a fully overwritten `len` read as `0x4141_4141`
+/// ("AAAA" in an ASCII dump) and `0xFF` makes it `u32::MAX`. Even a partial
+/// overwrite is enough: the first `0x41` alone turns `len` into 65.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum FillPattern {
+    /// The ramp `0, 1, 2, ...`: each byte shows where it was written
+    #[default]
+    Index,
+    /// The same byte everywhere (`const:NN`)
+    Constant(u8),
+    /// `A`, `B`, `C`, ... wrapping after `Z`, for readable `--ascii` dumps
+    Ascii,
+    /// Seeded PRNG bytes (`random`, or `--random-fill`)
+    Random,
+    /// The given bytes, cycled per index (`--fill-value`)
+    Bytes(Vec<u8>),
+}
+
+impl FillPattern {
+    /// The byte written at index `i` of the overflow.
+    ///
+    /// `Random` draws the next byte from `rng`, so the sequence depends on
+    /// every earlier draw; the other patterns ignore it.
+    pub(crate) fn byte_at(&self, i: usize, rng: Option<&mut rng::XorShift64>) -> u8 {
+        match self {
+            FillPattern::Index => i as u8,
+            FillPattern::Constant(byte) => *byte,
+            FillPattern::Ascii => b'A' + (i % 26) as u8,
+            FillPattern::Random => rng.expect("--fill random needs the seeded generator").next_u8(),
+            FillPattern::Bytes(bytes) => bytes[i % bytes.len()],
+        }
+    }
+}
+
+/// The byte order `--decode` and the field-value lines read the watched fields in (`--endian`).
+///
+/// Only the interpretation changes: the hex dump always shows the bytes as
+/// they sit in this machine's memory, and `safe_sum_prefix` still reads `len`
+/// the host's way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Endian {
+    #[default]
+    Little,
+    Big,
+}
+
+impl Endian {
+    /// The byte order of the machine running the demo
+    pub const HOST: Endian = if cfg!(target_endian = "big") { Endian::Big } else { Endian::Little };
+
+    /// `little-endian` or `big-endian`, for labels
+    pub fn name(self) -> &'static str {
+        match self {
+            Endian::Little => "little-endian",
+            Endian::Big => "big-endian",
+        }
+    }
+
+    /// The `u32` in the four bytes at `offset`, read in this byte order.
+    pub fn read_u32(self, bytes: &[u8], offset: usize) -> u32 {
+        let word: [u8; 4] = bytes[offset..offset + 4].try_into().unwrap();
+        match self {
+            Endian::Little => u32::from_le_bytes(word),
+            Endian::Big => u32::from_be_bytes(word),
+        }
+    }
+}
+
+/// How a [`MemoryView`] groups the snapshot when it draws a row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ViewMode {
+    /// One cell per byte, the classic hex dump
+    #[default]
+    Bytes,
+    /// One cell per 4-byte little-endian word, `0x0b0a0908`; a short last
+    /// word shows `..` for the bytes the view doesn't have (`--view words`)
+    Words,
+}
+
+/// Sleep between rows when `--delay` was given, so output animates.
+fn pause(delay: Option<std::time::Duration>) {
+    if let Some(delay) = delay {
+        output::flush();
+        std::thread::sleep(delay);
+    }
+}
+
+/// Block until the user presses Enter, if stdin is a terminal.
+fn wait_for_enter() {
+    use std::io::{stdin, BufRead, IsTerminal};
+
+    if !stdin().is_terminal() {
+        return;
+    }
+    out!("    (press Enter to continue) ");
+    output::flush();
+    let _ = stdin().lock().read_line(&mut String::new());
+}
+
+/// Wait for Enter after a write (`--step`), if stdin is a terminal.
+///
+/// Returns `false` when the user typed `q` to abort the current test.
+fn step_prompt() -> bool {
+    use std::io::{stdin, BufRead, IsTerminal};
+
+    if !stdin().is_terminal() {
+        return true;
+    }
+    out!("    (Enter: next write, q: abort this test) ");
+    output::flush();
+    let mut line = String::new();
+    let _ = stdin().lock().read_line(&mut line);
+    !line.trim().eq_ignore_ascii_case("q")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_writes_targets_named_offsets() {
+        let mut frame = Frame::new();
+        let len_off = Frame::len_offset();
+        let guard_off = Frame::guard_offset();
+
+        let bytes = apply_writes(&mut frame, &[(len_off, 0x09), (guard_off + 3, 0x00)]);
+
+        assert_eq!(bytes[len_off..len_off + 4], [0x09, 0x00, 0x00, 0x00]);
+        assert_eq!(bytes[guard_off..guard_off + 4], [0xef, 0xbe, 0xad, 0x00]);
+        assert_eq!(frame.read_len_volatile(), 9);
+        assert_eq!(frame.read_guard_volatile(), 0x00AD_BEEF);
+    }
+
+    #[test]
+    fn apply_writes_leaves_untouched_fields_alone() {
+        let mut frame = Frame::new();
+        let num_off = Frame::num_offset();
+
+        let bytes = apply_writes(&mut frame, &[(0, 0xAA), (BUFFER_SIZE - 1, 0xBB)]);
+
+        assert_eq!(bytes[..BUFFER_SIZE], [0xAA, 0, 0, 0, 0xBB]);
+        assert_eq!(bytes[num_off..num_off + 4], 40_000i32.to_le_bytes());
+        assert_eq!(frame.read_len_volatile(), BUFFER_SIZE as u32);
+    }
+
+    #[test]
+    fn safe_sum_prefix_sums_the_first_len_bytes() {
+        let mut frame = Frame::new();
+        apply_writes(&mut frame, &[(0, 10), (1, 20), (2, 30), (3, 40), (4, 50)]);
+        assert_eq!(safe_sum_prefix(&frame), 150);
+
+        frame.write_len_volatile(2);
+        assert_eq!(safe_sum_prefix(&frame), 30);
+    }
+
+    #[test]
+    fn safe_sum_prefix_panics_once_len_is_corrupted() {
+        let mut frame = Frame::new();
+        let base_ptr: *mut u8 = (&mut frame as *mut Frame).cast::<u8>();
+        unsafe { *base_ptr.add(LEN_OFF) = 10 };
+
+        assert_eq!(frame.read_len_volatile(), 10);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| safe_sum_prefix(&frame)));
+        assert!(result.is_err(), "buffer[..10] on a {BUFFER_SIZE}-byte buffer must panic");
+    }
+
+    #[test]
+    fn field_alarms_fire_once_per_field_with_the_write_index() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let mut view: MemoryView<FRAME_SIZE> = MemoryView::for_victim::<Frame>();
+        let log = Rc::clone(&seen);
+        view.add_field_alarm(Box::new(move |field, index| log.borrow_mut().push((field.to_string(), index))));
+
+        let mut prev = view.snapshot;
+        for (index, offset) in [(0, BUF_OFF), (8, LEN_OFF), (9, LEN_OFF + 1), (12, NUM_OFF)] {
+            view.snapshot[offset] = 0xAA;
+            color::with_theme(MARKERS, || view.print_write(&prev, index));
+            prev = view.snapshot;
+        }
+
+        assert_eq!(*seen.borrow(), [("len".to_string(), 8), ("num".to_string(), 12)]);
+    }
+
+    #[test]
+    fn watchpoint_fires_only_when_its_byte_changes() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let mut view: MemoryView<4> = MemoryView::new(&[], &[]);
+        let log = Rc::clone(&seen);
+        view.add_watchpoint(2, Box::new(move |old, new| log.borrow_mut().push((old, new))));
+
+        view.snapshot = [0, 9, 0, 0];
+        view.fire_watchpoints(&[0, 0, 0, 0]);
+        view.snapshot = [0, 9, 7, 0];
+        view.fire_watchpoints(&[0, 9, 0, 0]);
+
+        assert_eq!(*seen.borrow(), [(0, 7)]);
+    }
+
+    /// Markers only, so assertions don't depend on whether stdout is a TTY
+    const MARKERS: color::Theme = color::Theme {
+        ansi: Some(false),
+        ..color::Theme::DEFAULT
+    };
+
+    const SCHEME: color::ColorScheme = color::ColorScheme::DEFAULT;
+
+    #[test]
+    fn separator_stays_distinct_from_watched_range_start() {
+        // A field [4..8) that begins exactly at a separator
+        let view: MemoryView<8> = MemoryView::new(&[(4, 8)], &[4]);
+
+        color::with_theme(MARKERS, || {
+            assert_eq!(view.format_byte(&SCHEME, 4, 0x05, false), " | (05)");
+            assert_eq!(view.format_byte(&SCHEME, 4, 0x05, true), " | [05]");
+            assert_eq!(view.format_byte(&SCHEME, 5, 0x00, false), "(00)");
+        });
+    }
+
+    #[test]
+    fn separator_before_unwatched_byte_is_unchanged() {
+        let view: MemoryView<8> = MemoryView::new(&[(4, 8)], &[2]);
+
+        color::with_theme(MARKERS, || {
+            assert_eq!(view.format_byte(&SCHEME, 2, 0x07, false), " | 07 ");
+        });
+    }
+
+    #[test]
+    fn render_row_matches_the_printed_dump() {
+        let mut view: MemoryView<4> = MemoryView::new(&[(2, 4)], &[2]);
+        view.snapshot = [0x00, 0x01, 0x05, 0x00];
+
+        color::with_theme(MARKERS, || {
+            assert_eq!(view.render_row("init"), "init   | 00  01  | (05)(00)");
+        });
+    }
+
+    #[test]
+    fn word_view_groups_little_endian_words_and_pads_a_short_last_one() {
+        let mut view: MemoryView<10> = MemoryViewBuilder::new().watched_ranges(&[(4, 8)]).separators(&[4]).build();
+        view.view_mode = ViewMode::Words;
+        let prev = [0u8; 10];
+        view.snapshot = [0, 0, 0, 0, 0x05, 0, 0, 0, 0xAB, 0xCD];
+
+        color::with_theme(MARKERS, || {
+            assert_eq!(view.render_row("init"), "init   | 0x00000000  |(0x00000005) 0x....cdab ");
+            assert_eq!(view.render_diff(&prev, "i=0"), "i=0    | 0x00000000  |[0x00000005][0x....cdab]");
+        });
+    }
+
+    #[test]
+    fn render_diff_highlights_changes_and_appends_bit_lines() {
+        let mut view: MemoryView<4> = MemoryView::new(&[(2, 4)], &[2]);
+        view.show_bits = true;
+        view.snapshot = [0x00, 0x00, 0x07, 0x00];
+
+        color::with_theme(MARKERS, || {
+            assert_eq!(
+                view.render_diff(&[0x00, 0x00, 0x05, 0x00], "i=2"),
+                "i=2    | 00  00  | [07](00)\n       byte 0x02: 00000101 -> 00000111  (bit 1 flipped)"
+            );
+            // Corrupted now: drawn as old damage, no longer as a pristine watched byte
+            assert_eq!(view.render_row("after"), "after  | 00  00  | ~07~(00)");
+        });
+    }
+
+    #[test]
+    fn ascii_sidebar_marks_changes_like_the_hex() {
+        let mut view: MemoryView<4> = MemoryView::new(&[(2, 4)], &[2]);
+        view.ascii = true;
+        view.snapshot = [0x41, 0x00, 0x42, 0x7f];
+
+        color::with_theme(MARKERS, || {
+            assert_eq!(
+                view.render_diff(&[0x41, 0x00, 0x00, 0x7f], "i=2"),
+                "i=2    | 41  00  | [42](7f)   A  . [B](.)"
+            );
+        });
+    }
+
+    #[test]
+    fn offset_gutter_prefixes_the_row() {
+        let mut view: MemoryView<2> = MemoryView::new(&[], &[]);
+        view.show_offsets = true;
+
+        color::with_theme(MARKERS, || {
+            assert_eq!(view.render_row("init"), "0x0000 init   | 00  00 ");
+        });
+    }
+
+    #[test]
+    fn wrapped_rows_keep_separators_and_offsets() {
+        let mut view: MemoryView<6> = MemoryView::new(&[(4, 6)], &[2, 4]);
+        view.show_offsets = true;
+        view.bytes_per_row = 4;
+
+        color::with_theme(MARKERS, || {
+            assert_eq!(
+                view.render_row("init"),
+                "0x0000 init   | 00  00  | 00  00 \n0x0004        | (00)(00)"
+            );
+        });
+    }
+
+    #[test]
+    fn history_keeps_every_diff_until_cleared() {
+        let mut view: MemoryView<4> = MemoryView::new(&[(2, 4)], &[2]);
+        view.render_diff(&[0; 4], "init");
+        view.snapshot = [0x00, 0x01, 0x02, 0x00];
+        view.render_diff(&[0; 4], "i=1");
+
+        let timeline: Vec<(&str, [u8; 4], &[usize])> = view
+            .history()
+            .iter()
+            .map(|snapshot| (snapshot.label.as_str(), snapshot.bytes, &snapshot.changed[..]))
+            .collect();
+        assert_eq!(timeline, [("init", [0; 4], &[][..]), ("i=1", [0, 1, 2, 0], &[1, 2][..])]);
+        assert_eq!(view.history()[1].kinds[2], ByteKind::Changed);
+
+        view.clear_history();
+        assert!(view.history().is_empty());
+        // Byte 2 is intact again as far as the next test is concerned
+        view.render_diff(&view.snapshot.clone(), "next");
+        assert_eq!(view.history()[0].kinds[2], ByteKind::Watched);
+    }
+
+    #[test]
+    fn render_diff_flags_and_remembers_exactly_the_changed_bytes() {
+        let mut view: MemoryView<6> = MemoryView::new(&[(2, 6)], &[2]);
+        let initial = view.snapshot;
+
+        view.snapshot = [0, 7, 0, 9, 0, 0];
+        view.render_diff(&initial, "i=0");
+        assert_eq!(view.history()[0].changed, [1, 3]);
+        assert_eq!(view.corrupted, [false, true, false, true, false, false]);
+
+        // Byte 3 goes back to its original value: still corrupted, since it changed once
+        let prev = view.snapshot;
+        view.snapshot = [0, 7, 0, 0, 0, 0];
+        view.render_diff(&prev, "i=1");
+        assert_eq!(view.history()[1].changed, [3]);
+        assert_eq!(view.corrupted, [false, true, false, true, false, false]);
+        assert_eq!(view.classify(3, false), ByteKind::Corrupted);
+        assert_eq!(view.classify(4, false), ByteKind::Watched);
+    }
+
+    #[test]
+    fn watched_ranges_and_separators_respect_their_boundaries() {
+        let view: MemoryView<8> = MemoryView::new(&[(2, 4), (6, 8)], &[2, 6]);
+
+        let watched: Vec<usize> = (0..8).filter(|&i| view.is_watched(i)).collect();
+        assert_eq!(watched, [2, 3, 6, 7], "start is inclusive, end is exclusive");
+
+        let separated: Vec<usize> = (0..8).filter(|&i| view.is_separator(i)).collect();
+        assert_eq!(separated, [2, 6]);
+    }
+
+    #[test]
+    fn separators_fall_on_the_field_boundaries() {
+        let fields: MemoryView<FRAME_SIZE> = MemoryView::with_field_boundaries(WATCHED);
+        assert_eq!(fields.separators, [LEN_OFF, NUM_OFF, GUARD_OFF]);
+
+        // A victim's view also separates the buffer from the padding after it
+        let frame: MemoryView<FRAME_SIZE> = MemoryView::for_victim::<Frame>();
+        assert_eq!(frame.separators, [BUF_OFF + BUFFER_SIZE, LEN_OFF, NUM_OFF, GUARD_OFF]);
+
+        // No padding: the buffer's end and len's start share one separator
+        type Wide = SizedFrame<8>;
+        let wide: MemoryView<20> = MemoryView::for_victim::<Wide>();
+        assert_eq!(wide.separators, [8, 12, 16]);
+    }
+
+    #[test]
+    fn corruption_stats_split_watched_fields_into_full_and_partial() {
+        let mut view: MemoryView<FRAME_SIZE> = MemoryView::for_victim::<Frame>();
+        let initial = view.snapshot;
+        // Two bytes of buffer, all of len, and the low byte of num
+        for i in [BUF_OFF, BUF_OFF + 1, LEN_OFF, LEN_OFF + 1, LEN_OFF + 2, LEN_OFF + 3, NUM_OFF] {
+            view.snapshot[i] = 0xAA;
+        }
+        color::with_theme(MARKERS, || view.render_diff(&initial, "i=1"));
+
+        let stats = view.corruption_stats();
+        assert_eq!(stats.corrupted_bytes, 7);
+        assert_eq!(stats.watched_bytes_hit, 5);
+        let names = |fields: Vec<&FieldDamage>| fields.iter().map(|f| f.label()).collect::<Vec<_>>();
+        assert_eq!(names(stats.fully_corrupted().collect()), ["len"]);
+        assert_eq!(names(stats.partially_corrupted().collect()), ["num"]);
+        assert_eq!(stats.fields[2].bytes_hit, 0);
+
+        // A plain view doesn't know the names, so it falls back to the ranges
+        let unnamed: MemoryView<FRAME_SIZE> = MemoryView::with_field_boundaries(WATCHED);
+        assert_eq!(unnamed.corruption_stats().fields[0].label(), format!("[{LEN_OFF}..{})", LEN_OFF + LEN_SZ));
+    }
+
+    #[test]
+    fn a_built_view_draws_like_one_configured_by_hand() {
+        let mut by_hand: MemoryView<8> = MemoryView::new(&[(4, 8)], &[4]);
+        by_hand.padding_ranges = &[(2, 4)];
+        by_hand.ascii = true;
+        by_hand.show_offsets = true;
+        by_hand.bytes_per_row = 4;
+        let mut built: MemoryView<8> = MemoryViewBuilder::new()
+            .watched_ranges(&[(4, 8)])
+            .separators(&[4])
+            .padding_ranges(&[(2, 4)])
+            .ascii(true)
+            .show_offsets(true)
+            .bytes_per_row(4)
+            .build();
+        by_hand.snapshot = *b"AB\0\0len!";
+        built.snapshot = by_hand.snapshot;
+
+        color::with_theme(MARKERS, || assert_eq!(built.render_row("init"), by_hand.render_row("init")));
+
+        // Its own scheme wins over the theme's, even on a terminal
+        let colored = color::Theme { ansi: Some(true), ..color::Theme::DEFAULT };
+        let mono: MemoryView<2> = MemoryViewBuilder::new()
+            .watched_ranges(&[(0, 2)])
+            .color_scheme(color::ColorScheme::monochrome())
+            .build();
+        color::with_theme(colored, || assert_eq!(mono.render_row("init"), "init   |(00)(00)"));
+    }
+
+    #[test]
+    fn padding_bytes_are_braced_until_the_overflow_reaches_them() {
+        let mut view: MemoryView<4> = MemoryView::new(&[(2, 4)], &[1, 2]);
+        view.padding_ranges = &[(1, 2)];
+        view.snapshot = [0x00, 0x07, 0x05, 0x00];
+
+        color::with_theme(MARKERS, || {
+            assert_eq!(view.render_row("init"), "init   | 00  | {07} | (05)(00)");
+            assert_eq!(view.render_diff(&[0x00, 0x00, 0x05, 0x00], "i=1"), "i=1    | 00  | [07] | (05)(00)");
+            // Written padding is old damage from then on, not untouched padding
+            assert_eq!(view.render_row("after"), "after  | 00  | ~07~ | (05)(00)");
+        });
+    }
+
+    #[test]
+    fn compare_marks_what_differs_between_the_two_frames_only() {
+        let mut view: MemoryView<4> = MemoryView::new(&[(2, 4)], &[2]);
+        view.snapshot = [0x00, 0x00, 0x05, 0x00];
+        view.mark_changes(&[0x09; 4]);
+        let before = view.snapshot;
+        view.snapshot[1] = 0x07;
+        view.snapshot[2] = 0x08;
+
+        color::with_theme(MARKERS, || {
+            assert_eq!(
+                view.render_compare(&before, ("before", "after")),
+                "before | 00 [00] | [05](00)\nafter  | 00 [07] | [08](00)"
+            );
+            let same = view.render_compare(&view.snapshot, ("a", "b"));
+            assert_eq!(same, "a      | 00  07  | (08)(00)\nb      | 00  07  | (08)(00)");
+        });
+    }
+
+    #[test]
+    fn the_legend_lists_only_what_the_dump_can_show() {
+        let mut view: MemoryView<4> = MemoryView::new(&[(2, 4)], &[2]);
+        color::with_theme(MARKERS, || {
+            let legend = view.render_legend(false);
+            assert!(legend.starts_with("Legend:\n  (xx) = watched field, not yet corrupted\n  [xx] = byte changed"));
+            assert!(legend.ends_with("   xx  = plain byte\n   |   = field boundary"));
+            assert!(!legend.contains("{xx}") && !legend.contains("!xx!") && !legend.contains(">xx<"));
+        });
+
+        view.padding_ranges = &[(1, 2)];
+        view.sentinel = Some((0, GUARD_SENTINEL));
+        view.ascii = true;
+        view.color_scheme = Some(color::ColorScheme::colorblind());
+        color::with_theme(MARKERS, || {
+            let legend = view.render_legend(true);
+            for entry in ["<xx> = watched", "{xx} = padding", "!xx! = guard", ">xx< = byte safe code", "as ASCII"] {
+                assert!(legend.contains(entry), "{entry:?} missing from:\n{legend}");
+            }
+        });
+    }
+
+    #[test]
+    fn a_dead_sentinel_is_banged_until_it_holds_its_value_again() {
+        let mut view: MemoryView<5> = MemoryView::new(&[(1, 5)], &[1]);
+        view.sentinel = Some((1, 0xDEAD_BEEF));
+        view.snapshot = [0x00, 0xef, 0xbe, 0xad, 0xde];
+
+        color::with_theme(MARKERS, || {
+            assert_eq!(view.render_row("init"), "init   | 00  | (ef)(be)(ad)(de)");
+            let prev = view.snapshot;
+            view.snapshot[2] = 0x11;
+            assert_eq!(view.render_diff(&prev, "i=1"), "i=1    | 00  | !ef![11]!ad!!de!");
+            let prev = view.snapshot;
+            view.snapshot[2] = 0xbe;
+            assert_eq!(view.render_diff(&prev, "i=2"), "i=2    | 00  | (ef)[be](ad)(de)");
+        });
+    }
+
+    #[test]
+    fn the_same_bytes_decode_differently_in_each_byte_order() {
+        let bytes = [0xFF, 0x05, 0x00, 0x00, 0x00];
+        assert_eq!(Endian::Little.read_u32(&bytes, 1), 5);
+        assert_eq!(Endian::Big.read_u32(&bytes, 1), 0x0500_0000);
+        assert_eq!(Endian::Big.read_u32(&bytes, 0), 0xFF05_0000);
+    }
+
+    #[test]
+    fn plain_reads_see_the_overflow_too() {
+        let mut frame = Frame::new();
+        let base_ptr: *mut u8 = (&mut frame as *mut Frame).cast::<u8>();
+        for i in 0..FRAME_SIZE - BUF_OFF {
+            unsafe { *base_ptr.add(BUF_OFF + i) = i as u8 };
+        }
+
+        let volatile = (frame.read_len_volatile(), frame.read_num_volatile(), frame.read_guard_volatile());
+        assert_eq!((frame.read_len_plain(), frame.read_num_plain(), frame.read_guard_plain()), volatile);
+        assert_ne!(volatile.2, GUARD_SENTINEL);
+    }
+
+    #[test]
+    fn check_invariant_names_every_broken_part() {
+        let frame = Frame::new();
+        assert_eq!(frame.check_invariant(), Ok(()));
+
+        let mut frame = Frame::new();
+        apply_writes(&mut frame, &[(LEN_OFF, 0x09)]);
+        assert_eq!(frame.check_invariant(), Err("len = 9 > 5".to_string()));
+
+        apply_writes(&mut frame, &[(GUARD_OFF, 0x00)]);
+        assert_eq!(frame.check_invariant(), Err("len = 9 > 5; guard = 0xDEADBE00, not 0xDEADBEEF".to_string()));
+
+        let frame = Frame::with_guard(0xCAFE_BABE);
+        assert_eq!(frame.check_invariant_with_guard(0xCAFE_BABE), Ok(()));
+        assert_eq!(frame.check_invariant(), Err("guard = 0xCAFEBABE, not 0xDEADBEEF".to_string()));
+    }
+
+    #[test]
+    fn every_consumer_trusts_len_and_panics_past_the_buffer() {
+        let mut frame = Frame::new();
+        apply_writes(&mut frame, &[(BUF_OFF, 7), (BUF_OFF + 1, 9), (LEN_OFF, 2)]);
+        let results: Vec<String> = [Consumer::Sum, Consumer::Max, Consumer::Copy].map(|c| c.run(&frame)).into();
+        assert_eq!(results, ["16", "9", "[07, 09]"]);
+
+        apply_writes(&mut frame, &[(LEN_OFF, 0x06)]);
+        for consumer in [Consumer::Sum, Consumer::Max, Consumer::Copy] {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| consumer.run(&frame)));
+            assert!(result.is_err(), "{} should panic on len = 6", consumer.name());
+        }
+    }
+
+    #[test]
+    fn fill_patterns_pick_the_byte_for_each_index() {
+        let bytes = |pattern: FillPattern| {
+            let mut rng = rng::XorShift64::new(1);
+            (0..28).map(|i| pattern.byte_at(i, Some(&mut rng))).collect::<Vec<u8>>()
+        };
+
+        assert_eq!(bytes(FillPattern::Index)[..4], [0, 1, 2, 3]);
+        assert_eq!(bytes(FillPattern::Constant(0x41)), [0x41; 28]);
+        assert_eq!(bytes(FillPattern::Ascii)[24..], *b"YZAB");
+        assert_eq!(bytes(FillPattern::Bytes(vec![7, 9]))[..3], [7, 9, 7]);
+        assert_eq!(bytes(FillPattern::Random), bytes(FillPattern::Random));
+    }
+
+    #[test]
+    fn num_decodes_as_a_signed_little_endian_i32() {
+        let mut frame = Frame::new();
+        let mut view: MemoryView<FRAME_SIZE> = MemoryView::for_victim::<Frame>();
+        view.snapshot = apply_writes(&mut frame, &[]);
+        assert_eq!(view.read_u32(LEN_OFF), BUFFER_SIZE as u32);
+        assert_eq!(view.read_i32(NUM_OFF), 40_000);
+
+        let all_ones: Vec<(usize, u8)> = (NUM_OFF..NUM_OFF + 4).map(|offset| (offset, 0xFF)).collect();
+        view.snapshot = apply_writes(&mut frame, &all_ones);
+        assert_eq!(view.read_i32(NUM_OFF), -1);
+        assert_eq!(view.read_i32(NUM_OFF), frame.read_num_volatile());
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the")]
+    fn apply_writes_rejects_offsets_past_the_frame() {
+        apply_writes(&mut Frame::new(), &[(FRAME_SIZE, 0xFF)]);
+    }
+}
@@ -0,0 +1,124 @@
+//! Where the human-readable output goes.
+//!
+//! Everything the demo prints for people goes through [`out!`] and
+//! [`outln!`] instead of `print!`/`println!`. By default that's stdout, but
+//! machine-readable modes (like `--jsonl`) need stdout to themselves, so they
+//! can point the human output somewhere else, or nowhere.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::{Mutex, PoisonError};
+
+/// The current destination; `None` means stdout.
+static SINK: Mutex<Option<Box<dyn Write + Send>>> = Mutex::new(None);
+
+/// Send all subsequent human-readable output to `sink`.
+pub fn set_sink(sink: Box<dyn Write + Send>) {
+    *SINK.lock().unwrap_or_else(PoisonError::into_inner) = Some(sink);
+}
+
+/// Throw human-readable output away (for modes that own stdout).
+pub fn silence() {
+    set_sink(Box::new(io::sink()));
+}
+
+/// Write formatted output to the current sink. Use [`out!`] instead.
+pub fn write_fmt(args: fmt::Arguments) {
+    let mut sink = SINK.lock().unwrap_or_else(PoisonError::into_inner);
+    // Like print!, a closed pipe isn't worth crashing a teaching demo over
+    let _ = match sink.as_mut() {
+        Some(sink) => sink.write_fmt(args),
+        None => io::stdout().write_fmt(args),
+    };
+}
+
+/// Flush the current sink (before sleeping, waiting for input, or exiting).
+pub fn flush() {
+    let mut sink = SINK.lock().unwrap_or_else(PoisonError::into_inner);
+    let _ = match sink.as_mut() {
+        Some(sink) => sink.flush(),
+        None => io::stdout().flush(),
+    };
+}
+
+/// Writes everything to two sinks, e.g. the terminal and a transcript file (`--tee`).
+pub struct Tee<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A: Write, B: Write> Tee<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<A: Write, B: Write> Write for Tee<A, B> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.first.write_all(buf)?;
+        self.second.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.first.flush()?;
+        self.second.flush()
+    }
+}
+
+/// Removes ANSI SGR sequences (`ESC [ ... m`) on the way through (`--tee-strip-color`).
+///
+/// Tracks state across calls, so a sequence split between two writes is still
+/// removed whole.
+pub struct StripAnsi<W> {
+    inner: W,
+    in_escape: bool,
+}
+
+impl<W: Write> StripAnsi<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, in_escape: false }
+    }
+}
+
+impl<W: Write> Write for StripAnsi<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut plain = Vec::with_capacity(buf.len());
+        for &byte in buf {
+            match (self.in_escape, byte) {
+                (false, 0x1b) => self.in_escape = true,
+                (false, _) => plain.push(byte),
+                // The final byte of a CSI sequence is in 0x40..=0x7E; `m` for colors
+                (true, 0x40..=0x7e) if byte != b'[' => self.in_escape = false,
+                (true, _) => {}
+            }
+        }
+        self.inner.write_all(&plain)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// `print!` to the human-readable output sink.
+#[macro_export]
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::output::write_fmt(format_args!($($arg)*))
+    };
+}
+
+/// `println!` to the human-readable output sink.
+#[macro_export]
+macro_rules! outln {
+    () => {
+        $crate::output::write_fmt(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::write_fmt(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+pub use crate::{out, outln};
@@ -0,0 +1,88 @@
+//! `--quiz`: predict each test's outcome before it runs.
+//!
+//! Before every write length the student answers "will `safe_sum_prefix`
+//! succeed?", then watches the writes and gets told whether they were right.
+//! The answer key is the test's own [`TestOutcome`](crate::report::TestOutcome),
+//! so the quiz can never disagree with what the dump shows.
+
+use std::io::{stdin, BufRead, IsTerminal};
+
+use crate::output::{self, out, outln};
+
+/// Running score for one quiz session.
+#[derive(Debug, Default)]
+pub struct Quiz {
+    /// Size of the buffer being overflowed, for the question text
+    buffer_size: usize,
+    correct: usize,
+    asked: usize,
+    tests: usize,
+    /// Stdin wasn't a terminal, so no question was ever put
+    no_terminal: bool,
+}
+
+impl Quiz {
+    pub fn new(buffer_size: usize) -> Self {
+        Self {
+            buffer_size,
+            ..Self::default()
+        }
+    }
+
+    /// Ask for a prediction about writing `write_len` bytes.
+    ///
+    /// Returns `Some(true)` for "it will succeed", `Some(false)` for "it will
+    /// panic", or `None` when there's nobody to ask (stdin isn't a terminal)
+    /// or the answer wasn't y/n.
+    pub fn ask(&mut self, write_len: usize) -> Option<bool> {
+        self.tests += 1;
+        if !stdin().is_terminal() {
+            self.no_terminal = true;
+            return None;
+        }
+
+        outln!();
+        outln!("QUIZ: writing {write_len} bytes into a {}-byte buffer.", self.buffer_size);
+        out!("      Will safe_sum_prefix() succeed afterwards? [y/n] ");
+        output::flush();
+
+        let mut answer = String::new();
+        stdin().lock().read_line(&mut answer).ok()?;
+        match answer.trim().to_ascii_lowercase().as_str() {
+            "y" | "yes" => Some(true),
+            "n" | "no" => Some(false),
+            _ => {
+                outln!("      (not a y/n answer, this one doesn't count)");
+                None
+            }
+        }
+    }
+
+    /// Reveal the real outcome and score the prediction, if there was one.
+    pub fn reveal(&mut self, prediction: Option<bool>, panicked: bool) {
+        let Some(predicted_success) = prediction else { return };
+
+        self.asked += 1;
+        let actual = if panicked { "it panicked" } else { "it succeeded" };
+        if predicted_success != panicked {
+            self.correct += 1;
+            outln!("QUIZ: correct, {actual}. Score {}/{}", self.correct, self.asked);
+        } else {
+            outln!("QUIZ: wrong, {actual}. Score {}/{}", self.correct, self.asked);
+        }
+    }
+
+    /// Final tally, out of the number of tests in the run.
+    pub fn print_score(&self) {
+        outln!("=======================================================");
+        if self.no_terminal {
+            outln!("   QUIZ: no predictions (stdin is not a terminal)");
+        } else if self.asked == 0 {
+            outln!("   QUIZ: no predictions (no answer was y/n)");
+        } else {
+            outln!("   QUIZ SCORE: {}/{} correct", self.correct, self.tests);
+        }
+        outln!("=======================================================");
+        outln!();
+    }
+}
@@ -0,0 +1,495 @@
+//! What actually happened during a run, as data.
+//!
+//! The demo loop records one [`TestOutcome`] per write length; the
+//! [`RunReport`] aggregates them into the end-of-run summary table, report
+//! card, and the process exit code, and an [`OffsetHistogram`] tallies them
+//! byte by byte for `--histogram`. With `--format json` it also records a full
+//! [`TestRun`] per test, every write included, for tooling to consume.
+
+use crate::color;
+use crate::json::{Object, ToJson};
+use crate::layout::Layout;
+use crate::output::outln;
+use crate::CorruptionStats;
+
+/// The result of one write-length test.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestOutcome {
+    /// How many bytes the test wrote starting at `buffer[0]`
+    pub write_len: usize,
+
+    /// Names of the watched fields whose bytes differ from their initial value
+    pub corrupted_fields: Vec<&'static str>,
+
+    /// `len`, `num`, and `guard` as read back after the last write (0 if the layout lacks one)
+    pub len: u32,
+    pub num: i32,
+    pub guard: u32,
+
+    /// Did the safe consumer (`safe_sum_prefix`, unless the run picked another) panic?
+    pub panicked: bool,
+
+    /// Byte-level damage as the test's view tallied it; `None` for `--sweep`,
+    /// which runs without a view
+    pub stats: Option<CorruptionStats>,
+}
+
+/// How a test ended, from best to worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// No watched field changed and nothing panicked
+    Safe,
+    /// A watched field changed but safe code carried on regardless
+    SilentlyCorrupted,
+    /// Safe code tripped over the corruption and panicked
+    Panicked,
+}
+
+impl Verdict {
+    /// Short label for tables
+    pub fn label(self) -> &'static str {
+        match self {
+            Verdict::Safe => "safe",
+            Verdict::SilentlyCorrupted => "corrupt",
+            Verdict::Panicked => "PANIC",
+        }
+    }
+}
+
+impl TestOutcome {
+    /// Compare a test's initial and final bytes field by field.
+    ///
+    /// Only fields overlapping `watched` count: writes into the buffer are
+    /// the ones the code meant to make.
+    pub fn new(
+        write_len: usize,
+        layout: &Layout,
+        watched: &[(usize, usize)],
+        initial: &[u8],
+        last: &[u8],
+        panicked: bool,
+    ) -> Self {
+        let corrupted_fields = layout
+            .fields
+            .iter()
+            .filter(|f| watched.iter().any(|&(start, end)| f.offset < end && start < f.end()))
+            .filter(|f| initial[f.offset..f.end()] != last[f.offset..f.end()])
+            .map(|f| f.name)
+            .collect();
+
+        Self {
+            write_len,
+            corrupted_fields,
+            len: read_u32(layout, last, "len"),
+            num: read_u32(layout, last, "num") as i32,
+            guard: read_u32(layout, last, "guard"),
+            panicked,
+            stats: None,
+        }
+    }
+
+    /// Attach the test's [`CorruptionStats`] for the summary table.
+    pub fn with_stats(mut self, stats: CorruptionStats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    pub fn verdict(&self) -> Verdict {
+        if self.panicked {
+            Verdict::Panicked
+        } else if self.corrupted_fields.is_empty() {
+            Verdict::Safe
+        } else {
+            Verdict::SilentlyCorrupted
+        }
+    }
+}
+
+/// The little-endian `u32` in field `name` of `bytes`, or 0 if there's no such 4-byte field.
+fn read_u32(layout: &Layout, bytes: &[u8], name: &str) -> u32 {
+    match layout.field(name) {
+        Some(f) if f.size == 4 => u32::from_le_bytes(bytes[f.offset..f.end()].try_into().unwrap()),
+        _ => 0,
+    }
+}
+
+/// `12 bytes (8 watched): len, num 2/4`, or just the byte count when no watched field was hit.
+fn describe_damage(stats: &CorruptionStats) -> String {
+    let fields: Vec<String> = stats
+        .fields
+        .iter()
+        .filter(|f| f.bytes_hit > 0)
+        .map(|f| match f.is_fully_corrupted() {
+            true => f.label(),
+            false => format!("{} {}/{}", f.label(), f.bytes_hit, f.size()),
+        })
+        .collect();
+    let bytes = format!("{} bytes", stats.corrupted_bytes);
+    if fields.is_empty() {
+        bytes
+    } else {
+        format!("{bytes} ({} watched): {}", stats.watched_bytes_hit, fields.join(", "))
+    }
+}
+
+/// Every outcome from one run of the demo.
+#[derive(Debug, Clone, Default)]
+pub struct RunReport {
+    pub outcomes: Vec<TestOutcome>,
+}
+
+impl RunReport {
+    pub fn push(&mut self, outcome: TestOutcome) {
+        self.outcomes.push(outcome);
+    }
+
+    /// Number of tests that ended with the given verdict
+    pub fn count(&self, verdict: Verdict) -> usize {
+        self.outcomes.iter().filter(|o| o.verdict() == verdict).count()
+    }
+
+    /// How many tests corrupted each field, most-corrupted first.
+    pub fn field_hits(&self) -> Vec<(&'static str, usize)> {
+        let mut hits: Vec<(&'static str, usize)> = Vec::new();
+        for name in self.outcomes.iter().flat_map(|o| &o.corrupted_fields) {
+            match hits.iter_mut().find(|(n, _)| n == name) {
+                Some((_, count)) => *count += 1,
+                None => hits.push((name, 1)),
+            }
+        }
+        // Stable sort keeps layout order among ties
+        hits.sort_by_key(|&(_, n)| std::cmp::Reverse(n));
+        hits
+    }
+
+    /// Process exit code for this run: the worst verdict across all tests.
+    ///
+    /// - `0`: no test corrupted a watched field
+    /// - `1`: something was corrupted, but nothing panicked
+    /// - `2`: at least one safe consumer panicked
+    pub fn exit_code(&self) -> i32 {
+        if self.count(Verdict::Panicked) > 0 {
+            2
+        } else if self.count(Verdict::SilentlyCorrupted) > 0 {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Print one row per outcome, marking where each field is first corrupted.
+    ///
+    /// Meant for `--sweep`, where the outcomes are consecutive write lengths,
+    /// so the markers land exactly on the thresholds.
+    pub fn print_sweep_table(&self) {
+        outln!("  {:>5}  {:<8} {:<20} first", "len", "outcome", "corrupted");
+        outln!("  {:>5}  {:<8} {:<20} -----", "---", "-------", "---------");
+
+        let mut seen: Vec<&'static str> = Vec::new();
+        let mut panicked_yet = false;
+        for outcome in &self.outcomes {
+            let mut firsts: Vec<String> = outcome
+                .corrupted_fields
+                .iter()
+                .filter(|name| !seen.contains(name))
+                .map(|name| format!("◀ {name}"))
+                .collect();
+            seen.extend(&outcome.corrupted_fields);
+            if outcome.panicked && !panicked_yet {
+                panicked_yet = true;
+                firsts.push("◀ panic".to_string());
+            }
+
+            let corrupted = if outcome.corrupted_fields.is_empty() {
+                "-".to_string()
+            } else {
+                outcome.corrupted_fields.join(",")
+            };
+            let row = format!(
+                "  {:>5}  {:<8} {:<20} {}",
+                outcome.write_len,
+                outcome.verdict().label(),
+                corrupted,
+                firsts.join(" ")
+            );
+            outln!("{}", row.trim_end());
+        }
+    }
+
+    /// Print one aligned row per test with the final field values, so the
+    /// trend from more writes to more corruption reads top to bottom.
+    ///
+    /// `PANIC` is drawn in the "changed" color when ANSI is on; it's padded
+    /// first so the escape codes don't throw the columns off. The last column
+    /// is the view's [`CorruptionStats`]: bytes changed, how many of them were
+    /// watched, and each field hit, with `n/size` when only part of it was.
+    pub fn print_summary_table(&self) {
+        outln!("  {:>6}  {:>10}  {:>11}  {:>10}  outcome  damage", "writes", "len", "num", "guard");
+        outln!("  {:>6}  {:>10}  {:>11}  {:>10}  -------  ------", "------", "---", "---", "-----");
+        for outcome in &self.outcomes {
+            let verdict = outcome.verdict();
+            let padded = format!("{:<7}", verdict.label());
+            let label = match verdict {
+                Verdict::Panicked => color::changed_text(&padded),
+                _ => padded,
+            };
+            let damage = outcome.stats.as_ref().map_or("-".to_string(), describe_damage);
+            outln!(
+                "  {:>6}  {:>10}  {:>11}  {:>10}  {label}  {damage}",
+                outcome.write_len,
+                outcome.len,
+                outcome.num,
+                format!("0x{:08X}", outcome.guard)
+            );
+        }
+    }
+
+    /// Print the boxed end-of-run summary.
+    pub fn print_report_card(&self) {
+        let hits = self.field_hits();
+        let most_corrupted = if hits.is_empty() {
+            "none".to_string()
+        } else {
+            hits.iter()
+                .map(|(name, n)| format!("{name} ({n})"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let lines = [
+            "CORRUPTION REPORT CARD".to_string(),
+            String::new(),
+            format!("tests run:            {}", self.outcomes.len()),
+            format!("stayed safe:          {}", self.count(Verdict::Safe)),
+            format!("silently corrupted:   {}", self.count(Verdict::SilentlyCorrupted)),
+            format!("panicked:             {}", self.count(Verdict::Panicked)),
+            format!("most corrupted:       {most_corrupted}"),
+            String::new(),
+            format!("exit code:            {}", self.exit_code()),
+        ];
+
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        outln!("╔{}╗", "═".repeat(width + 4));
+        for line in &lines {
+            let pad = width - line.chars().count();
+            outln!("║  {line}{}  ║", " ".repeat(pad));
+        }
+        outln!("╚{}╝", "═".repeat(width + 4));
+    }
+}
+
+/// How many tests corrupted each byte offset of the frame (`--histogram`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OffsetHistogram {
+    /// Tests added so far
+    pub tests: usize,
+
+    /// For each offset, how many of those tests changed it at least once
+    pub counts: Vec<usize>,
+}
+
+impl OffsetHistogram {
+    /// An empty histogram for a frame of `size` bytes
+    pub fn new(size: usize) -> Self {
+        Self { tests: 0, counts: vec![0; size] }
+    }
+
+    /// Count one test, given which bytes it corrupted (a `MemoryView`'s `corrupted` array).
+    pub fn add(&mut self, corrupted: &[bool]) {
+        self.tests += 1;
+        for (count, &hit) in self.counts.iter_mut().zip(corrupted) {
+            *count += usize::from(hit);
+        }
+    }
+
+    /// Print one bar per offset, one `#` per test that corrupted it, labeled
+    /// with the field that owns the byte.
+    pub fn print(&self, layout: &Layout) {
+        outln!("Corrupted in how many of the {} tests, by offset:", self.tests);
+        for (offset, &count) in self.counts.iter().enumerate() {
+            let field = layout.field_index_at(offset).map_or("(pad)", |index| layout.fields[index].name);
+            let bar = format!("{:<width$}", "#".repeat(count), width = self.tests);
+            outln!("  offset {offset:>2}: {bar}  {count}/{}  {field}", self.tests);
+        }
+    }
+}
+
+/// One unchecked write and the frame it left behind (`--format json`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteStep {
+    /// Which write of the test this was (`i` in the demo loop)
+    pub index: usize,
+
+    /// Offset from the start of the frame the byte landed at
+    pub offset: usize,
+
+    pub value: u8,
+
+    /// Every byte of the frame after the write
+    pub snapshot: Vec<u8>,
+
+    /// Offsets that differ from the snapshot before the write
+    pub changed: Vec<usize>,
+}
+
+impl ToJson for WriteStep {
+    fn write_json(&self, out: &mut String) {
+        Object::new()
+            .field("index", self.index)
+            .field("offset", self.offset)
+            .field("value", self.value)
+            .field("snapshot", &self.snapshot)
+            .field("changed", &self.changed)
+            .write_json(out);
+    }
+}
+
+/// Everything that happened in one test, as one JSON object (`--format json`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestRun {
+    /// Index of the test within the run
+    pub test: usize,
+
+    pub write_len: usize,
+
+    /// Where each field of the frame lives
+    pub layout: &'static Layout,
+
+    /// The frame before the first write
+    pub initial: Vec<u8>,
+
+    pub steps: Vec<WriteStep>,
+
+    /// `len`, `num`, and `guard` as read back after the last write
+    pub len: u32,
+    pub num: i32,
+    pub guard: u32,
+
+    /// Did the safe consumer (`safe_sum_prefix`, unless the run picked another) panic?
+    pub panicked: bool,
+}
+
+impl TestRun {
+    /// A test that hasn't written anything yet; the final values are filled in by the caller.
+    pub fn new(test: usize, write_len: usize, layout: &'static Layout, initial: &[u8]) -> Self {
+        Self {
+            test,
+            write_len,
+            layout,
+            initial: initial.to_vec(),
+            steps: Vec::new(),
+            len: 0,
+            num: 0,
+            guard: 0,
+            panicked: false,
+        }
+    }
+
+    /// The whole test as one line of compact JSON
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.write_json(&mut out);
+        out
+    }
+}
+
+impl ToJson for TestRun {
+    fn write_json(&self, out: &mut String) {
+        let fields: Vec<Object> = self
+            .layout
+            .fields
+            .iter()
+            .map(|f| Object::new().field("name", f.name).field("offset", f.offset).field("size", f.size))
+            .collect();
+        Object::new()
+            .field("test", self.test)
+            .field("write_len", self.write_len)
+            .field("fields", &fields)
+            .field("initial", &self.initial)
+            .field("steps", &self.steps)
+            .field("len", self.len)
+            .field("num", self.num)
+            .field("guard", self.guard)
+            .field("panicked", self.panicked)
+            .write_json(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{FieldInfo, Layout};
+
+    #[test]
+    fn test_run_serializes_every_step() {
+        static LAYOUT: Layout = Layout {
+            name: "Tiny",
+            repr: "repr(C)",
+            size: 2,
+            align: 1,
+            fields: &[FieldInfo { name: "a", offset: 0, size: 2, align: 1 }],
+        };
+        let mut run = TestRun::new(0, 1, &LAYOUT, &[0, 0]);
+        run.steps.push(WriteStep { index: 0, offset: 0, value: 7, snapshot: vec![7, 0], changed: vec![0] });
+        run.len = 7;
+        run.panicked = true;
+
+        assert_eq!(
+            run.to_json(),
+            "{\"test\":0,\"write_len\":1,\"fields\":[{\"name\":\"a\",\"offset\":0,\"size\":2}],\
+             \"initial\":[0,0],\"steps\":[{\"index\":0,\"offset\":0,\"value\":7,\"snapshot\":[7,0],\
+             \"changed\":[0]}],\"len\":7,\"num\":0,\"guard\":0,\"panicked\":true}"
+        );
+    }
+
+    #[test]
+    fn outcomes_read_back_the_final_field_values() {
+        static LAYOUT: Layout = Layout {
+            name: "Pair",
+            repr: "repr(C)",
+            size: 8,
+            align: 4,
+            fields: &[
+                FieldInfo { name: "len", offset: 0, size: 4, align: 4 },
+                FieldInfo { name: "num", offset: 4, size: 4, align: 4 },
+            ],
+        };
+        let last = [9, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF];
+        let outcome = TestOutcome::new(3, &LAYOUT, &[(0, 8)], &[0; 8], &last, true);
+
+        assert_eq!((outcome.len, outcome.num, outcome.guard), (9, -1, 0));
+        assert_eq!(outcome.corrupted_fields, ["len", "num"]);
+        assert_eq!(outcome.verdict(), Verdict::Panicked);
+    }
+
+    #[test]
+    fn exit_code_is_the_worst_verdict() {
+        static LAYOUT: Layout = Layout {
+            name: "Len",
+            repr: "repr(C)",
+            size: 4,
+            align: 4,
+            fields: &[FieldInfo { name: "len", offset: 0, size: 4, align: 4 }],
+        };
+        let outcome = |last: [u8; 4], panicked| TestOutcome::new(4, &LAYOUT, &[(0, 4)], &[5, 0, 0, 0], &last, panicked);
+
+        let mut report = RunReport::default();
+        report.push(outcome([5, 0, 0, 0], false));
+        assert_eq!(report.exit_code(), 0);
+        report.push(outcome([4, 0, 0, 0], false));
+        assert_eq!(report.exit_code(), 1);
+        report.push(outcome([9, 0, 0, 0], true));
+        assert_eq!(report.exit_code(), 2);
+    }
+
+    #[test]
+    fn histogram_counts_tests_per_offset() {
+        let mut histogram = OffsetHistogram::new(4);
+        histogram.add(&[true, false, false, false]);
+        histogram.add(&[true, true, false, false]);
+        histogram.add(&[true, true, true]);
+
+        assert_eq!(histogram.tests, 3);
+        assert_eq!(histogram.counts, [3, 2, 1, 0]);
+    }
+}
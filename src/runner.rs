@@ -0,0 +1,396 @@
+//! The overflow as a library call: outcomes back, nothing printed.
+//!
+//! [`run_scenario`] performs the same writes as the default demo, one fresh
+//! frame per write length, then runs the chosen safe consumer on what's left,
+//! and returns a [`TestOutcome`] for each: the final `len`/`num`/`guard`,
+//! whether the consumer panicked, and the byte-level
+//! [`CorruptionStats`](crate::CorruptionStats). It draws no dump and prints
+//! nothing, so tests and other binaries can assert on a run directly.
+//!
+//! [`record_scenario`] runs the same tests and also keeps the frame after
+//! every write. That's what the binary draws: the default demo and
+//! `--simulate` dump each test from its recording and report its outcome,
+//! and `--sweep` tabulates the outcomes alone.
+//!
+//! A run is a list of write lengths and how to perform them. The
+//! `--scenario` stories aren't: each one prints its own narrative around
+//! writes of its own, and reports no outcomes, so they have no `Config`.
+
+use std::mem::size_of;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Mutex, PoisonError};
+
+use crate::cli::{Direction, Location};
+use crate::report::{TestOutcome, TestRun, WriteStep};
+use crate::simulate::{field_u32, initial_bytes};
+use crate::victim::Victim;
+use crate::{
+    rng, Consumer, FillPattern, Frame, MemoryView, SizedFrame, BUFFER_SIZE, BUF_OFF, FRAME_SIZE, GUARD_SENTINEL,
+    LEN_OFF, MAX_BUFFER_SIZE, WATCHED,
+};
+
+/// What performs the writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// Real unchecked writes past a `SizedFrame`'s buffer, as in the default demo
+    #[default]
+    Overflow,
+    /// The `--miri-safe` model: in-bounds writes into a `[u8; FRAME_SIZE]`
+    /// (buffer size `BUFFER_SIZE` only)
+    Simulated,
+}
+
+/// Which overflow each test performs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Scenario {
+    /// Write the fill pattern over `Config::writes` bytes from `buffer[0]`, as the default demo does
+    #[default]
+    Fill,
+    /// One test, written just far enough to set `num` to this value, with
+    /// `len`'s own bytes written back over it (`--target-num`); `writes` is ignored
+    TargetNum(i32),
+}
+
+/// Everything a run needs to know, without any of the display options.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// Length of the frame's buffer, `1..=MAX_BUFFER_SIZE`
+    pub buffer_size: usize,
+
+    /// One test per entry: how many bytes to write starting at `buffer[0]`
+    pub writes: Vec<usize>,
+
+    /// Which overflow the tests perform
+    pub scenario: Scenario,
+
+    /// The safe code run on each corrupted frame; its panic is the outcome's `panicked`
+    pub consumer: Consumer,
+
+    /// What each write stores
+    pub fill: FillPattern,
+
+    /// Seed for `FillPattern::Random` and `random_init`; ignored otherwise
+    pub seed: u64,
+
+    pub mode: Mode,
+
+    /// Start each frame from random `num` (and `guard`, with `random_guard`)
+    /// instead of the fixed values, drawn before the test's fill bytes
+    pub random_init: bool,
+
+    /// What a fresh frame's guard holds, when it isn't random
+    pub guard: u32,
+
+    /// With `random_init`, draw the guard too
+    pub random_guard: bool,
+
+    /// Which end of each test's range is written first
+    pub direction: Direction,
+
+    /// A `len` stored into each fresh frame before the writes (`--len-from-env`)
+    pub initial_len: Option<u32>,
+
+    /// Whether each frame lives on the stack or in a `Box`
+    pub location: Location,
+}
+
+impl Default for Config {
+    /// The default demo's run: a 5-byte buffer and the `--writes` defaults
+    fn default() -> Self {
+        Self {
+            buffer_size: BUFFER_SIZE,
+            writes: crate::cli::DEFAULT_WRITES.to_vec(),
+            scenario: Scenario::Fill,
+            consumer: Consumer::Sum,
+            fill: FillPattern::Index,
+            seed: 0,
+            mode: Mode::Overflow,
+            random_init: false,
+            guard: GUARD_SENTINEL,
+            random_guard: false,
+            direction: Direction::Forward,
+            initial_len: None,
+            location: Location::Stack,
+        }
+    }
+}
+
+/// Run every write length in `config` and return one outcome per test, in order.
+///
+/// Each test starts from a fresh frame, so the outcomes are independent. A
+/// `Random` fill draws from one generator across the whole run, like the demo.
+///
+/// # Panics
+///
+/// If the buffer size is outside `1..=MAX_BUFFER_SIZE`, a write length would
+/// leave the frame, or `Mode::Simulated` is asked for anything but the plain
+/// model: a `BUFFER_SIZE` buffer, fixed initial values, forward writes.
+pub fn run_scenario(config: &Config) -> Vec<TestOutcome> {
+    record_scenario(config).into_iter().map(|test| test.outcome).collect()
+}
+
+/// One test of a run: its outcome, and the frame after every write that led there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedTest {
+    pub outcome: TestOutcome,
+
+    /// The pristine frame and each write's snapshot, in the form `--save-trace`
+    /// keeps, for drawing the test write by write without performing it again
+    pub run: TestRun,
+}
+
+/// `run_scenario`, keeping every write's snapshot next to each outcome.
+///
+/// # Panics
+///
+/// Exactly when `run_scenario` does.
+pub fn record_scenario(config: &Config) -> Vec<RecordedTest> {
+    let mut rng = rng::XorShift64::new(config.seed);
+    match config.mode {
+        Mode::Overflow => with_buffer_size!(config.buffer_size, overflow_sized(config, &mut rng)),
+        Mode::Simulated => {
+            assert_eq!(config.buffer_size, BUFFER_SIZE, "the simulated frame only has a {BUFFER_SIZE}-byte buffer");
+            let plain = config.scenario == Scenario::Fill
+                && !config.random_init
+                && config.guard == GUARD_SENTINEL
+                && config.direction == Direction::Forward
+                && config.initial_len.is_none()
+                && config.location == Location::Stack;
+            assert!(plain, "the simulated frame only models the plain demo");
+            simulated(config, &mut rng)
+        }
+    }
+}
+
+/// `run_scenario` for a real `SizedFrame<B>`; `N` is its size.
+fn overflow_sized<const B: usize, const N: usize>(config: &Config, rng: &mut rng::XorShift64) -> Vec<RecordedTest> {
+    let buf_off = SizedFrame::<B>::buffer_offset();
+    let writes = match config.scenario {
+        Scenario::Fill => config.writes.clone(),
+        Scenario::TargetNum(_) => vec![SizedFrame::<B>::num_offset() + SizedFrame::<B>::num_size() - buf_off],
+    };
+    writes
+        .into_iter()
+        .enumerate()
+        .map(|(test, end)| {
+            assert!(buf_off + end <= N, "writing {end} bytes would leave the {N}-byte Frame");
+            let fresh = match config.random_init {
+                true => SizedFrame::<B>::randomized(rng, config.guard, config.random_guard),
+                false => SizedFrame::<B>::with_guard(config.guard),
+            };
+            // Location::Heap: the same frame in a Box; everything below goes through `frame` either way
+            let (mut on_stack, mut on_heap);
+            let frame: &mut SizedFrame<B> = match config.location {
+                Location::Stack => {
+                    on_stack = fresh;
+                    &mut on_stack
+                }
+                Location::Heap => {
+                    on_heap = Box::new(fresh);
+                    &mut on_heap
+                }
+            };
+            if let Some(len) = config.initial_len {
+                frame.write_len_volatile(len);
+            }
+            frame.zero_padding();
+            let base_ptr = frame.base_ptr();
+
+            let mut view: MemoryView<N> = MemoryView::for_victim::<SizedFrame<B>>();
+            view.capture(base_ptr);
+            let initial = view.snapshot;
+            let mut run = TestRun::new(test, end, SizedFrame::<B>::LAYOUT, &initial);
+            for step in 0..end {
+                // `i` is the index written; Direction::Reverse visits them from the top down
+                let i = match config.direction {
+                    Direction::Forward => step,
+                    Direction::Reverse => end - 1 - step,
+                };
+                let filler = config.fill.byte_at(i, Some(&mut *rng));
+                let value = match config.scenario {
+                    Scenario::TargetNum(target) => aimed_value::<B, N>(target, &initial, buf_off + i, filler),
+                    Scenario::Fill => filler,
+                };
+                let prev = view.snapshot;
+
+                // THE DANGEROUS PART: this write has NO BOUNDS CHECK. Once
+                // `i >= B` it lands in `len`, `num`, and `guard`, which safe
+                // Rust would never allow; the assert above only keeps it
+                // inside the frame, so the damage stays observable.
+                unsafe { *base_ptr.add(buf_off + i) = value };
+
+                view.capture(base_ptr);
+                run.steps.push(write_step(i, buf_off + i, value, &prev, &view.snapshot));
+            }
+            view.mark_changes(&initial);
+
+            let panicked = panics(config.consumer, frame);
+            (run.len, run.num, run.guard, run.panicked) =
+                (frame.read_len_volatile(), frame.read_num_volatile(), frame.read_guard_volatile(), panicked);
+            let outcome =
+                TestOutcome::new(end, SizedFrame::<B>::LAYOUT, SizedFrame::<B>::WATCHED, &initial, &view.snapshot, panicked)
+                    .with_stats(view.corruption_stats());
+            RecordedTest { outcome, run }
+        })
+        .collect()
+}
+
+/// Run `consumer` on `frame` and say whether it panicked, keeping the panic
+/// message off stderr: the outcome reports the panic, and a run prints nothing.
+fn panics<const B: usize>(consumer: Consumer, frame: &SizedFrame<B>) -> bool {
+    // One swap at a time, so two runs on different threads can't each restore the other's silent hook
+    static HOOK: Mutex<()> = Mutex::new(());
+    let _swapping = HOOK.lock().unwrap_or_else(PoisonError::into_inner);
+
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| consumer.run(frame)));
+    std::panic::set_hook(hook);
+    result.is_err()
+}
+
+/// One write of a recording: which byte it stored, and the frame it left behind.
+fn write_step<const N: usize>(index: usize, offset: usize, value: u8, prev: &[u8; N], now: &[u8; N]) -> WriteStep {
+    WriteStep {
+        index,
+        offset,
+        value,
+        snapshot: now.to_vec(),
+        changed: (0..N).filter(|&b| prev[b] != now[b]).collect(),
+    }
+}
+
+/// The byte a `Scenario::TargetNum` overflow writes at `offset`.
+///
+/// `target`'s little-endian bytes over `num`, `len`'s original bytes back over
+/// `len` (from the pristine snapshot), and the usual `filler` everywhere else.
+/// The attacker chooses what the adjacent fields become instead of smashing them.
+fn aimed_value<const B: usize, const N: usize>(
+    target: i32,
+    initial: &[u8; N],
+    offset: usize,
+    filler: u8,
+) -> u8 {
+    let len_off = SizedFrame::<B>::len_offset();
+    let num_off = SizedFrame::<B>::num_offset();
+    if (num_off..num_off + SizedFrame::<B>::num_size()).contains(&offset) {
+        target.to_le_bytes()[offset - num_off]
+    } else if (len_off..len_off + SizedFrame::<B>::len_size()).contains(&offset) {
+        initial[offset]
+    } else {
+        filler
+    }
+}
+
+/// `run_scenario` on the byte model from `simulate`.
+fn simulated(config: &Config, rng: &mut rng::XorShift64) -> Vec<RecordedTest> {
+    config
+        .writes
+        .iter()
+        .enumerate()
+        .map(|(test, &end)| {
+            let mut view: MemoryView<FRAME_SIZE> = MemoryView::for_victim::<SizedFrame<BUFFER_SIZE>>();
+            view.snapshot = initial_bytes();
+            let initial = view.snapshot;
+            let mut run = TestRun::new(test, end, &crate::layout::FRAME, &initial);
+            for i in 0..end {
+                let value = config.fill.byte_at(i, Some(&mut *rng));
+                let prev = view.snapshot;
+                // Indexes the whole frame, not the buffer: that's the modelled bug
+                view.snapshot[BUF_OFF + i] = value;
+                run.steps.push(write_step(i, BUF_OFF + i, value, &prev, &view.snapshot));
+            }
+            view.mark_changes(&initial);
+
+            // The consumer gets a real Frame with the model's buffer and len, set through safe calls only
+            let mut frame = Frame::new();
+            frame.buffer.copy_from_slice(&view.snapshot[BUF_OFF..BUF_OFF + BUFFER_SIZE]);
+            frame.write_len_volatile(field_u32(&view.snapshot, LEN_OFF));
+            let panicked = panics(config.consumer, &frame);
+
+            let outcome = TestOutcome::new(end, &crate::layout::FRAME, WATCHED, &initial, &view.snapshot, panicked)
+                .with_stats(view.corruption_stats());
+            (run.len, run.num, run.guard, run.panicked) = (outcome.len, outcome.num, outcome.guard, panicked);
+            RecordedTest { outcome, run }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::Verdict;
+
+    #[test]
+    fn the_default_run_panics_once_len_is_reached() {
+        let outcomes = run_scenario(&Config::default());
+        let verdicts: Vec<(usize, Verdict)> = outcomes.iter().map(|o| (o.write_len, o.verdict())).collect();
+        assert_eq!(
+            verdicts,
+            [
+                (5, Verdict::Safe),
+                (6, Verdict::Safe),
+                (8, Verdict::Safe),
+                (10, Verdict::Panicked),
+                (12, Verdict::Panicked)
+            ]
+        );
+
+        let last = outcomes.last().unwrap();
+        assert_eq!(last.len, 0x0B0A_0908);
+        let stats = last.stats.as_ref().unwrap();
+        assert_eq!((stats.watched_bytes_hit, stats.fully_corrupted().count()), (4, 1));
+    }
+
+    #[test]
+    fn the_demo_options_shape_the_writes() {
+        let reach_guard = vec![FRAME_SIZE - BUF_OFF];
+        let aimed = run_scenario(&Config { scenario: Scenario::TargetNum(-7), ..Config::default() });
+        let aimed = &aimed[0];
+        assert_eq!((aimed.len, aimed.num, aimed.verdict()), (BUFFER_SIZE as u32, -7, Verdict::SilentlyCorrupted));
+
+        // Same bytes either way round; only the order they land in differs
+        let forward = run_scenario(&Config { writes: reach_guard.clone(), ..Config::default() });
+        let reverse = run_scenario(&Config { writes: reach_guard, direction: Direction::Reverse, ..Config::default() });
+        assert_eq!(forward, reverse);
+
+        let lying = run_scenario(&Config { writes: vec![0], initial_len: Some(99), ..Config::default() });
+        assert!(lying[0].panicked && lying[0].corrupted_fields.is_empty());
+    }
+
+    #[test]
+    fn a_recording_holds_every_write_and_the_consumer_decides_the_panic() {
+        let config = Config { writes: vec![6, 12], ..Config::default() };
+        let recorded = record_scenario(&config);
+        for test in &recorded {
+            assert_eq!(test.run.steps.len(), test.outcome.write_len);
+            let last = &test.run.steps.last().unwrap().snapshot;
+            assert_eq!(test.run.len, u32::from_le_bytes(last[LEN_OFF..LEN_OFF + 4].try_into().unwrap()));
+            assert_eq!(test.run.panicked, test.outcome.panicked);
+        }
+        assert_eq!(recorded[1].run.steps[7].changed, [BUF_OFF + 7]);
+
+        // Every consumer trusts len the same way, wherever the frame lives
+        for consumer in [Consumer::Max, Consumer::Copy] {
+            let heap = Config { consumer, location: Location::Heap, ..config.clone() };
+            assert_eq!(run_scenario(&heap), run_scenario(&config));
+        }
+    }
+
+    #[test]
+    fn the_model_agrees_with_the_real_frame() {
+        let config = Config {
+            writes: (0..=FRAME_SIZE - BUF_OFF).collect(),
+            fill: FillPattern::Random,
+            seed: 7,
+            ..Config::default()
+        };
+        let real = run_scenario(&config);
+        let model = run_scenario(&Config { mode: Mode::Simulated, ..config });
+
+        let fields = |outcomes: &[TestOutcome]| -> Vec<(u32, i32, u32, bool)> {
+            outcomes.iter().map(|o| (o.len, o.num, o.guard, o.panicked)).collect()
+        };
+        assert_eq!(fields(&real), fields(&model));
+    }
+}
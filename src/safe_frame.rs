@@ -0,0 +1,85 @@
+//! The "right way": keep the invariant inside a type that can't break it.
+//!
+//! [`SafeFrame`] wraps the same [`Frame`], but its field is private and every
+//! way to change the buffer is bounds-checked. `len <= BUFFER_SIZE` is no
+//! longer something safe code hopes for; it's something the API guarantees,
+//! so [`SafeFrame::sum_prefix`] has nothing left to trust.
+
+use std::fmt;
+
+use crate::victim::Victim;
+use crate::{Frame, BUFFER_SIZE};
+
+/// A `Frame` whose `len` can never exceed `BUFFER_SIZE`.
+///
+/// Same memory layout as `Frame` (it's a single-field wrapper), so it can be
+/// dumped with the same `MemoryView`.
+#[repr(transparent)]
+pub struct SafeFrame(Frame);
+
+/// `push` was called on a full buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Full;
+
+impl fmt::Display for Full {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "buffer is full ({BUFFER_SIZE} bytes)")
+    }
+}
+
+impl SafeFrame {
+    /// An empty frame: `len == 0`, guard and num as in `Frame::new`.
+    pub fn new() -> Self {
+        let frame = Frame::new();
+        frame.write_len_volatile(0);
+        Self(frame)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.read_len_volatile() as usize
+    }
+
+    /// Append one byte, or refuse if that would go past the buffer.
+    pub fn push(&mut self, byte: u8) -> Result<(), Full> {
+        let len = self.len();
+        if len >= BUFFER_SIZE {
+            return Err(Full);
+        }
+        self.0.buffer[len] = byte;
+        self.0.write_len_volatile(len as u32 + 1);
+        Ok(())
+    }
+
+    /// Sum the bytes pushed so far. Never panics: `take` can't overrun the array.
+    pub fn sum_prefix(&self) -> u64 {
+        self.0.buffer.iter().take(self.len()).map(|&b| b as u64).sum()
+    }
+
+    /// Zero the wrapped frame's padding, so dumping it shows no stale bytes
+    pub(crate) fn zero_padding(&mut self) {
+        self.0.zero_padding();
+    }
+
+    /// The wrapped frame, read-only (for inspection and dumping)
+    pub fn as_frame(&self) -> &Frame {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_refuses_to_overflow() {
+        let mut frame = SafeFrame::new();
+        for byte in 0..BUFFER_SIZE as u8 {
+            assert_eq!(frame.push(byte), Ok(()));
+        }
+        assert_eq!(frame.push(0xFF), Err(Full));
+
+        assert_eq!(frame.len(), BUFFER_SIZE);
+        assert_eq!(frame.as_frame().read_guard_volatile(), 0xDEAD_BEEF);
+        assert_eq!(frame.sum_prefix(), (0..BUFFER_SIZE as u64).sum());
+    }
+}
@@ -0,0 +1,84 @@
+//! The bug and the crash, three function calls apart.
+//!
+//! "The crash happens in safe code" is easy to nod along to when both are in
+//! the same loop. Here the frame is built in `load()`, corrupted in
+//! `corrupt()`, and only used in `use_it()`. By the time anything goes wrong,
+//! `corrupt()` has long since returned, and the panic points at `use_it()`.
+
+use std::panic::AssertUnwindSafe;
+
+use super::Scenario;
+use crate::cli::Options;
+use crate::output::outln;
+use crate::victim::Victim;
+use crate::{safe_sum_prefix, Frame, MemoryView, BUFFER_SIZE, BUF_OFF, FRAME_SIZE};
+
+pub struct CallChain;
+
+/// How many bytes `corrupt()` writes into the `BUFFER_SIZE`-byte buffer
+const WRITE_LEN: usize = 12;
+
+/// Build a perfectly valid frame.
+fn load() -> Frame {
+    Frame::new()
+}
+
+/// The actual bug: an unchecked copy that runs past `buffer`.
+///
+/// Returns normally. Nothing here panics, asserts, or looks wrong from outside.
+fn corrupt(frame: &mut Frame) {
+    let buf_ptr = (frame as *mut Frame).cast::<u8>();
+    for i in 0..WRITE_LEN {
+        unsafe { *buf_ptr.add(BUF_OFF + i) = i as u8 };
+    }
+}
+
+/// Innocent safe code, far away from the bug.
+fn use_it(frame: &Frame) -> u64 {
+    safe_sum_prefix(frame)
+}
+
+impl Scenario for CallChain {
+    fn name(&self) -> &'static str {
+        "call-chain"
+    }
+
+    fn description(&self) -> &'static str {
+        "The bug is in corrupt(), the panic surfaces two calls later in use_it()"
+    }
+
+    fn run(&self, opts: &Options) {
+        outln!("=======================================================");
+        outln!("   SCENARIO: the bug is in corrupt(), the crash is in use_it()");
+        outln!("=======================================================\n");
+
+        let mut view: MemoryView<FRAME_SIZE> = MemoryView::for_victim::<Frame>();
+        view.apply_display_options(opts);
+
+        outln!("main()");
+        outln!("├─ load()               builds a valid Frame");
+        let mut frame = load();
+        frame.zero_padding();
+        view.capture((&frame as *const Frame).cast::<u8>());
+        let prev = view.snapshot;
+        view.print_row("load");
+
+        outln!("├─ corrupt(&mut frame)  writes {WRITE_LEN} bytes into a {BUFFER_SIZE}-byte buffer, returns normally");
+        corrupt(&mut frame);
+        view.capture((&frame as *const Frame).cast::<u8>());
+        view.print_diff(&prev, "bug");
+        outln!("│    ...corrupt() has returned; len is now {}", frame.read_len_volatile());
+
+        outln!("└─ use_it(&frame)       safe code, calls safe_sum_prefix()");
+        match std::panic::catch_unwind(AssertUnwindSafe(|| use_it(&frame))) {
+            Ok(sum) => outln!("     use_it() = {sum}"),
+            Err(_) => {
+                outln!("     PANIC in use_it() -> safe_sum_prefix()");
+                outln!();
+                outln!("The backtrace points at use_it(), a function with no unsafe code.");
+                outln!("corrupt() smashed len, but use_it() is where it crashed: the stack");
+                outln!("frame that caused the damage is gone by the time anyone notices.");
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! A corrupted length fed to `copy_nonoverlapping`.
+//!
+//! `MemoryView::capture` copies a fixed `N` bytes, which is fine. Real code
+//! usually takes the count from a length field instead, and that field is
+//! exactly what an overflow corrupts. Here one stray byte lands on
+//! `Frame.len`, and a helper that "knows" `len <= BUFFER_SIZE` copies that
+//! many bytes into a `BUFFER_SIZE`-byte destination.
+
+use std::cell::UnsafeCell;
+use std::mem::{offset_of, size_of};
+use std::ptr;
+
+use super::Scenario;
+use crate::cli::Options;
+use crate::output::outln;
+use crate::victim::Victim;
+use crate::{Frame, MemoryView, BUFFER_SIZE, FRAME_SIZE, LEN_OFF};
+
+pub struct CopyLen;
+
+/// The destination of the copy: a buffer followed by a canary.
+///
+/// The canary lives in the same allocation so the overrun stays observable
+/// (and stays inside memory we own). In real code, whatever happened to sit
+/// after `dst` would be overwritten instead.
+#[repr(C)]
+struct CopyDest {
+    dst: [u8; BUFFER_SIZE],
+    canary: UnsafeCell<u32>,
+}
+
+const DEST_SIZE: usize = size_of::<CopyDest>();
+const CANARY_OFF: usize = offset_of!(CopyDest, canary);
+const CANARY: u32 = 0xCAFE_F00D;
+
+const DEST_WATCHED: &[(usize, usize)] = &[(CANARY_OFF, CANARY_OFF + 4)];
+const DEST_SEPS: &[usize] = &[BUFFER_SIZE, CANARY_OFF];
+
+/// The value our single stray byte writes into `len`.
+///
+/// Chosen so the copy overruns `dst` into the canary but never leaves
+/// `CopyDest` (or reads past `Frame`), so the demo itself stays observable.
+const CORRUPT_LEN: u8 = 11;
+
+const _: () = assert!(CORRUPT_LEN as usize <= DEST_SIZE);
+const _: () = assert!(CORRUPT_LEN as usize <= FRAME_SIZE);
+
+impl CopyDest {
+    fn new() -> Self {
+        Self {
+            dst: [0u8; BUFFER_SIZE],
+            canary: UnsafeCell::new(CANARY),
+        }
+    }
+
+    fn read_canary_volatile(&self) -> u32 {
+        unsafe { ptr::read_volatile(self.canary.get()) }
+    }
+}
+
+/// Copy the first `len` bytes of the frame into `dest.dst`.
+///
+/// Looks like a careful little helper: one `unsafe` block with a
+/// justification. The justification is only as good as `len`.
+fn copy_prefix(frame: &Frame, dest: &mut CopyDest) -> usize {
+    let len = frame.read_len_volatile() as usize;
+
+    // SAFETY (claimed): Frame's invariant says len <= BUFFER_SIZE, so we read
+    // within `buffer` and write within `dst`. Corrupt `len` and both halves of
+    // that argument are false.
+    unsafe {
+        let src = (frame as *const Frame).cast::<u8>();
+        let dst = (dest as *mut CopyDest).cast::<u8>();
+        ptr::copy_nonoverlapping(src, dst, len);
+    }
+
+    len
+}
+
+impl Scenario for CopyLen {
+    fn name(&self) -> &'static str {
+        "copy-len"
+    }
+
+    fn description(&self) -> &'static str {
+        "One stray byte in len turns a copy_nonoverlapping into a second overflow"
+    }
+
+    fn run(&self, opts: &Options) {
+        outln!("=======================================================");
+        outln!("   SCENARIO: corrupted length -> copy_nonoverlapping");
+        outln!("=======================================================\n");
+
+        let mut frame = Frame::new();
+        frame.buffer = [0xA1, 0xA2, 0xA3, 0xA4, 0xA5];
+        frame.zero_padding();
+        let base_ptr: *mut u8 = (&mut frame as *mut Frame).cast::<u8>();
+
+        // --------------------------------------------------------------------
+        // Source: one stray write lands on len
+        // --------------------------------------------------------------------
+        let mut src_view: MemoryView<FRAME_SIZE> = MemoryView::for_victim::<Frame>();
+        src_view.apply_display_options(opts);
+        src_view.capture(base_ptr);
+        let prev = src_view.snapshot;
+
+        outln!("Source Frame (len is the watched field at offset {LEN_OFF}):");
+        src_view.print_row("init");
+
+        // The bug: a single unchecked write at the wrong offset
+        unsafe { *base_ptr.add(LEN_OFF) = CORRUPT_LEN };
+
+        src_view.capture(base_ptr);
+        src_view.print_diff(&prev, "stray");
+        outln!("len is now {} (buffer holds {BUFFER_SIZE})\n", frame.read_len_volatile());
+
+        // --------------------------------------------------------------------
+        // Destination: copy_nonoverlapping trusts len
+        // --------------------------------------------------------------------
+        let mut dest = CopyDest::new();
+        let dest_ptr: *mut u8 = (&mut dest as *mut CopyDest).cast::<u8>();
+        // Zero the padding between dst and the canary so the dump doesn't show stale bytes
+        unsafe { std::ptr::write_bytes(dest_ptr.add(BUFFER_SIZE), 0, CANARY_OFF - BUFFER_SIZE) };
+
+        let mut dest_view: MemoryView<DEST_SIZE> = MemoryView::new(DEST_WATCHED, DEST_SEPS);
+        dest_view.apply_display_options(opts);
+        dest_view.capture(dest_ptr);
+        let prev = dest_view.snapshot;
+
+        outln!("Destination (dst[{BUFFER_SIZE}] then a canary at offset {CANARY_OFF}):");
+        dest_view.print_row("init");
+
+        let copied = copy_prefix(&frame, &mut dest);
+
+        dest_view.capture(dest_ptr);
+        dest_view.print_diff(&prev, "copy");
+
+        let canary = dest.read_canary_volatile();
+        outln!(
+            "copy_prefix() copied {copied} bytes into a {BUFFER_SIZE}-byte buffer: {} bytes of overrun",
+            copied.saturating_sub(BUFFER_SIZE)
+        );
+        if canary == CANARY {
+            outln!("canary intact: 0x{canary:08X}");
+        } else {
+            outln!("canary SMASHED: was 0x{CANARY:08X}, now 0x{canary:08X}");
+        }
+
+        outln!();
+        outln!("The visualization's own copy uses a constant N and is fine. The moment");
+        outln!("the count comes from a field an overflow can reach, one stray byte turns");
+        outln!("a careful-looking copy into a second, bigger overflow.");
+    }
+}
@@ -0,0 +1,145 @@
+//! One byte past a buffer flips what a destructor does.
+//!
+//! `DropFrame` holds a 4-byte buffer followed by an `AuditLog`, whose `Drop`
+//! either flushes its entries or throws them away depending on an enum tag.
+//! The tag sits right after the buffer, so a 5-byte copy into the 4-byte
+//! buffer lands its last byte on the tag. Nothing looks wrong at the copy:
+//! the damage only shows up later, when the frame goes out of scope and the
+//! destructor quietly takes the other branch.
+//!
+//! The stray byte is `2`, a valid `OnDrop` discriminant, so the tag is still a
+//! legal value and `drop` runs soundly. Any other byte would make the tag an
+//! invalid enum, and matching on it would be UB: the write is chosen here
+//! instead of coming from `--fill` for that reason.
+
+use std::cell::UnsafeCell;
+use std::mem::{offset_of, size_of};
+
+use super::Scenario;
+use crate::cli::Options;
+use crate::output::outln;
+use crate::MemoryView;
+
+pub struct DropOrder;
+
+const BUF_LEN: usize = 4;
+
+/// What `AuditLog` does with its entries when it is dropped
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OnDrop {
+    Flush = 1,
+    Discard = 2,
+}
+
+/// A log whose destructor decides the fate of its entries.
+#[repr(C)]
+struct AuditLog {
+    on_drop: UnsafeCell<OnDrop>,
+    entries: u32,
+}
+
+impl Drop for AuditLog {
+    fn drop(&mut self) {
+        // Sound: the overflow below only ever stores a valid discriminant
+        match unsafe { std::ptr::read_volatile(self.on_drop.get()) } {
+            OnDrop::Flush => outln!("  AuditLog::drop: flushed {} entries to the audit trail", self.entries),
+            OnDrop::Discard => outln!("  AuditLog::drop: discarded {} entries, nothing written", self.entries),
+        }
+    }
+}
+
+/// A buffer with a droppable field right behind it.
+#[repr(C)]
+struct DropFrame {
+    buffer: [u8; BUF_LEN],
+    log: AuditLog,
+}
+
+const SIZE: usize = size_of::<DropFrame>();
+const LOG_OFF: usize = offset_of!(DropFrame, log);
+const TAG_OFF: usize = LOG_OFF + offset_of!(AuditLog, on_drop);
+const ENTRIES_OFF: usize = LOG_OFF + offset_of!(AuditLog, entries);
+
+const WATCHED: &[(usize, usize)] = &[(TAG_OFF, TAG_OFF + 1), (ENTRIES_OFF, ENTRIES_OFF + 4)];
+const SEPS: &[usize] = &[TAG_OFF, TAG_OFF + 1, ENTRIES_OFF];
+const PADDING: &[(usize, usize)] = &[(TAG_OFF + 1, ENTRIES_OFF)];
+
+/// A 5-byte record copied into the 4-byte buffer; its last byte is `OnDrop::Discard`
+const RECORD: [u8; BUF_LEN + 1] = [0x10, 0x11, 0x12, 0x13, OnDrop::Discard as u8];
+
+impl DropFrame {
+    fn new() -> Self {
+        Self {
+            buffer: [0; BUF_LEN],
+            log: AuditLog {
+                on_drop: UnsafeCell::new(OnDrop::Flush),
+                entries: 3,
+            },
+        }
+    }
+
+    fn read_on_drop(&self) -> OnDrop {
+        unsafe { std::ptr::read_volatile(self.log.on_drop.get()) }
+    }
+}
+
+impl Scenario for DropOrder {
+    fn name(&self) -> &'static str {
+        "drop-order"
+    }
+
+    fn description(&self) -> &'static str {
+        "One byte past the buffer overwrites an enum tag, and a destructor takes the other branch"
+    }
+
+    fn run(&self, opts: &Options) {
+        outln!("=======================================================");
+        outln!("   SCENARIO: corruption that only shows up in Drop");
+        outln!("=======================================================\n");
+
+        outln!("#[repr(C)] struct DropFrame {{ buffer: [u8; {BUF_LEN}], log: AuditLog }}");
+        outln!("#[repr(C)] struct AuditLog {{ on_drop: OnDrop, entries: u32 }}  // impl Drop");
+        outln!("#[repr(u8)] enum OnDrop {{ Flush = 1, Discard = 2 }}");
+        outln!("on_drop sits at offset {TAG_OFF}, the first byte past buffer.\n");
+
+        outln!("First, an untouched frame going out of scope:");
+        drop(DropFrame::new());
+        outln!();
+
+        let mut frame = DropFrame::new();
+        let base_ptr: *mut u8 = (&mut frame as *mut DropFrame).cast::<u8>();
+        // Zero the padding behind the tag so the dump doesn't show stale bytes
+        for &(start, end) in PADDING {
+            unsafe { std::ptr::write_bytes(base_ptr.add(start), 0, end - start) };
+        }
+
+        let mut view: MemoryView<SIZE> = MemoryView::new(WATCHED, SEPS);
+        view.padding_ranges = PADDING;
+        view.apply_display_options(opts);
+        view.capture(base_ptr);
+        let mut prev = view.snapshot;
+
+        outln!("Now copy the {}-byte record {RECORD:02x?} into the {BUF_LEN}-byte buffer:", RECORD.len());
+        view.print_row("init");
+        for (i, &byte) in RECORD.iter().enumerate() {
+            unsafe { *base_ptr.add(i) = byte };
+            view.capture(base_ptr);
+            view.print_diff(&prev, &format!("i={i}"));
+            prev = view.snapshot;
+        }
+
+        outln!();
+        outln!("on_drop is now {:?}. Nothing has gone wrong yet: no panic, no bad", frame.read_on_drop());
+        outln!("length, every field still holds a legal value. The frame goes on to be");
+        outln!("used normally, and much later it goes out of scope:");
+        drop(frame);
+
+        outln!();
+        outln!("The bug was one byte in a copy; the symptom is three log entries that");
+        outln!("silently never reach the audit trail, reported by a destructor that ran");
+        outln!("exactly as written. Corrupted state doesn't have to crash anything to");
+        outln!("change what a program does: it just has to be read by some later code,");
+        outln!("and Drop is later code that runs on every path out of a scope.");
+    }
+}
@@ -0,0 +1,84 @@
+//! `len` set to `u32::MAX` directly, without touching anything else.
+//!
+//! The default demo reaches `len` by marching through the buffer, so the
+//! damage arrives mixed in with everything else the overflow hits. Here the
+//! writes go straight at `len`'s four bytes (`base_ptr.add(LEN_OFF)`), one
+//! `0xFF` at a time, and `num` and `guard` are never touched. One field is
+//! everything: safe code that trusts `len` asks for a slice of about four
+//! billion bytes out of a five-byte buffer.
+
+use std::panic::AssertUnwindSafe;
+
+use super::Scenario;
+use crate::cli::Options;
+use crate::output::outln;
+use crate::victim::Victim;
+use crate::{safe_sum_prefix, Frame, MemoryView, BUFFER_SIZE, FRAME_SIZE, LEN_OFF, LEN_SZ};
+
+pub struct LenOverflow;
+
+/// The byte written over each byte of `len`
+const FILL: u8 = 0xFF;
+
+impl Scenario for LenOverflow {
+    fn name(&self) -> &'static str {
+        "len-overflow"
+    }
+
+    fn description(&self) -> &'static str {
+        "Only len's four bytes are overwritten with 0xFF: a ~4-billion-byte slice from safe code"
+    }
+
+    fn run(&self, opts: &Options) {
+        outln!("=======================================================");
+        outln!("   SCENARIO: len overwritten with 0xFFFFFFFF");
+        outln!("=======================================================\n");
+
+        let mut frame = Frame::new();
+        frame.zero_padding();
+        let base_ptr: *mut u8 = (&mut frame as *mut Frame).cast::<u8>();
+
+        let mut view: MemoryView<FRAME_SIZE> = MemoryView::for_victim::<Frame>();
+        view.apply_display_options(opts);
+        view.capture(base_ptr);
+        let mut prev = view.snapshot;
+
+        outln!(
+            "Before: len={}, num={}, guard=0x{:08X}",
+            frame.read_len_volatile(),
+            frame.read_num_volatile(),
+            frame.read_guard_volatile()
+        );
+        view.print_row("init");
+
+        // Aim at len itself: no buffer bytes, no neighbours, just its 4 bytes
+        for i in 0..LEN_SZ {
+            unsafe { *base_ptr.add(LEN_OFF + i) = FILL };
+            view.capture(base_ptr);
+            view.print_diff(&prev, &format!("len+{i}"));
+            prev = view.snapshot;
+        }
+
+        outln!(
+            "After:  len={}, num={}, guard=0x{:08X}",
+            frame.read_len_volatile(),
+            frame.read_num_volatile(),
+            frame.read_guard_volatile()
+        );
+
+        let len = frame.read_len_volatile();
+        outln!("safe_sum_prefix() slices buffer[..{len}] out of a {BUFFER_SIZE}-byte buffer:");
+        match std::panic::catch_unwind(AssertUnwindSafe(|| safe_sum_prefix(&frame))) {
+            Ok(sum) => outln!("safe_sum_prefix() = {sum} (len was still valid)"),
+            Err(_) => outln!(
+                "safe_sum_prefix() PANICKED! (len = {len:#X}: a slice of ~{} GiB)",
+                (len as u64).div_ceil(1 << 30)
+            ),
+        }
+
+        outln!();
+        outln!("num and guard are exactly as they started, and the buffer was never");
+        outln!("written. Four bytes were enough: the bounds check caught it here, but");
+        outln!("any unchecked code trusting len would read ~4 billion bytes past the buffer.");
+    }
+}
@@ -0,0 +1,85 @@
+//! A bulk `write_bytes` (memset) with a bad count.
+//!
+//! The default demo ramps through the overflow one byte at a time. Real
+//! overflows are often a single call: `memset(buf, 0xFF, count)` with a count
+//! computed for some other buffer. Here one `ptr::write_bytes` with a count
+//! past `BUFFER_SIZE` floods the rest of the `Frame` with `0xFF`, and `len`,
+//! `num`, and `guard` are all maxed out in the same instant.
+
+use std::panic::AssertUnwindSafe;
+use std::ptr;
+
+use super::Scenario;
+use crate::cli::Options;
+use crate::output::outln;
+use crate::victim::Victim;
+use crate::{safe_sum_prefix, Frame, MemoryView, BUFFER_SIZE, BUF_OFF, FRAME_SIZE};
+
+pub struct MemsetFill;
+
+/// The byte the fill writes
+const FILL: u8 = 0xFF;
+
+/// How many bytes the buggy fill writes: everything from `buffer` to the end
+/// of `Frame`, so the overrun never leaves memory we own.
+const FILL_COUNT: usize = FRAME_SIZE - BUF_OFF;
+
+const _: () = assert!(FILL_COUNT > BUFFER_SIZE);
+
+impl Scenario for MemsetFill {
+    fn name(&self) -> &'static str {
+        "memset-fill"
+    }
+
+    fn description(&self) -> &'static str {
+        "A single write_bytes with a bad count maxes out len, num, and guard at once"
+    }
+
+    fn run(&self, opts: &Options) {
+        outln!("=======================================================");
+        outln!("   SCENARIO: write_bytes (memset) with a bad count");
+        outln!("=======================================================\n");
+
+        let mut frame = Frame::new();
+        frame.zero_padding();
+        let base_ptr: *mut u8 = (&mut frame as *mut Frame).cast::<u8>();
+
+        let mut view: MemoryView<FRAME_SIZE> = MemoryView::for_victim::<Frame>();
+        view.apply_display_options(opts);
+        view.capture(base_ptr);
+        let prev = view.snapshot;
+
+        outln!(
+            "Before: len={}, num={}, guard=0x{:08X}",
+            frame.read_len_volatile(),
+            frame.read_num_volatile(),
+            frame.read_guard_volatile()
+        );
+        view.print_row("init");
+
+        // The bug: the count is for some bigger buffer, not this one
+        outln!("write_bytes(buffer, 0x{FILL:02X}, {FILL_COUNT})   // buffer holds {BUFFER_SIZE}");
+        unsafe { ptr::write_bytes(base_ptr.add(BUF_OFF), FILL, FILL_COUNT) };
+
+        // One call, one row: the whole tail changes at once
+        view.capture(base_ptr);
+        view.print_diff(&prev, "fill");
+
+        outln!(
+            "After:  len={}, num={}, guard=0x{:08X}",
+            frame.read_len_volatile(),
+            frame.read_num_volatile(),
+            frame.read_guard_volatile()
+        );
+
+        match std::panic::catch_unwind(AssertUnwindSafe(|| safe_sum_prefix(&frame))) {
+            Ok(sum) => outln!("safe_sum_prefix() = {sum} (len was still valid)"),
+            Err(_) => outln!("safe_sum_prefix() PANICKED! (len was filled to {})", frame.read_len_volatile()),
+        }
+
+        outln!();
+        outln!("There's no gradual ramp to watch here: a single bulk write with the");
+        outln!("wrong count sets every field past the buffer to its maximum at once.");
+        outln!("len = u32::MAX, num = -1, and the guard is gone, all in one call.");
+    }
+}
@@ -0,0 +1,77 @@
+//! Alternative demonstrations, selected with `--scenario <name>`.
+//!
+//! The default run (no `--scenario`) is the byte-by-byte overflow march in
+//! `main`. Each scenario here isolates one other way a corrupted value turns
+//! into a bug, reusing `Frame` and `MemoryView` so the output looks familiar.
+
+pub mod call_chain;
+pub mod copy_len;
+pub mod drop_order;
+pub mod len_overflow;
+pub mod memset_fill;
+pub mod multi_buffer;
+pub mod nonzero_niche;
+pub mod ptr_field;
+pub mod repr_rust;
+pub mod safe_contrast;
+pub mod safe_frame;
+pub mod silent;
+pub mod union_val;
+pub mod use_after_free;
+pub mod volatile_plain;
+pub mod wire_format;
+
+use crate::cli::Options;
+use crate::output::outln;
+
+/// A self-contained demonstration.
+pub trait Scenario {
+    /// Name used with `--scenario`
+    fn name(&self) -> &'static str;
+
+    /// One line for `--list-scenarios`
+    fn description(&self) -> &'static str;
+
+    /// Run the demonstration, printing its output
+    fn run(&self, opts: &Options);
+}
+
+/// Every registered scenario, in the order they're listed to users.
+pub const ALL: &[&dyn Scenario] = &[
+    &copy_len::CopyLen,
+    &multi_buffer::MultiBuffer,
+    &memset_fill::MemsetFill,
+    &silent::Silent,
+    &len_overflow::LenOverflow,
+    &union_val::UnionVal,
+    &wire_format::WireFormat,
+    &call_chain::CallChain,
+    &safe_frame::SafeFrameScenario,
+    &safe_contrast::SafeContrast,
+    &nonzero_niche::NonzeroNiche,
+    &drop_order::DropOrder,
+    &ptr_field::PtrField,
+    &repr_rust::ReprRust,
+    &use_after_free::UseAfterFree,
+    &volatile_plain::VolatileVsPlain,
+];
+
+/// Look up a scenario by its command-line name.
+pub fn find(name: &str) -> Option<&'static dyn Scenario> {
+    ALL.iter().copied().find(|s| s.name() == name)
+}
+
+/// Print every scenario with its description (`--list-scenarios`).
+pub fn print_list() {
+    let width = ALL.iter().map(|s| s.name().len()).max().unwrap_or(0);
+    outln!("Scenarios (run one with --scenario <NAME>, or every one with --scenario all):");
+    outln!();
+    for scenario in ALL {
+        outln!("  {:<width$}  {}", scenario.name(), scenario.description());
+    }
+}
+
+/// Comma-separated scenario names, for error messages.
+pub fn names() -> String {
+    ALL.iter().map(|s| s.name()).collect::<Vec<_>>().join(", ")
+}
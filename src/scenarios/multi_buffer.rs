@@ -0,0 +1,155 @@
+//! Two buffers, one length: which overflow actually matters?
+//!
+//! Real structs rarely have a single array. Overflowing an array that's
+//! followed by *more data* scribbles on that data (bad, but the program's
+//! control decisions are unaffected). Overflowing the array that's followed
+//! by a *control field* hands the attacker the program's logic.
+
+use std::cell::UnsafeCell;
+use std::mem::{align_of, offset_of, size_of};
+use std::panic::AssertUnwindSafe;
+use std::ptr;
+
+use super::Scenario;
+use crate::cli::Options;
+use crate::output::outln;
+use crate::layout::{field, FieldInfo, Layout};
+use crate::MemoryView;
+
+pub struct MultiBuffer;
+
+const BUF_LEN: usize = 4;
+
+/// A victim with two adjacent buffers before its length field.
+///
+/// `len` describes how much of `buf_b` is valid.
+#[repr(C)]
+struct MultiBufFrame {
+    buf_a: [u8; BUF_LEN],
+    buf_b: [u8; BUF_LEN],
+    len: UnsafeCell<u32>,
+}
+
+pub const LAYOUT: Layout = Layout {
+    name: "MultiBufFrame",
+    repr: "repr(C)",
+    size: size_of::<MultiBufFrame>(),
+    align: align_of::<MultiBufFrame>(),
+    fields: &[
+        field!(MultiBufFrame, buf_a: [u8; BUF_LEN]),
+        field!(MultiBufFrame, buf_b: [u8; BUF_LEN]),
+        field!(MultiBufFrame, len: u32),
+    ],
+};
+
+const SIZE: usize = size_of::<MultiBufFrame>();
+const BUF_A_OFF: usize = offset_of!(MultiBufFrame, buf_a);
+const BUF_B_OFF: usize = offset_of!(MultiBufFrame, buf_b);
+const LEN_OFF: usize = offset_of!(MultiBufFrame, len);
+
+const WATCHED: &[(usize, usize)] = &[(LEN_OFF, LEN_OFF + 4)];
+const SEPS: &[usize] = &[BUF_B_OFF, LEN_OFF];
+
+/// How far past the end of the chosen buffer each overflow writes
+const OVERFLOW: usize = 2;
+
+impl MultiBufFrame {
+    fn new() -> Self {
+        Self {
+            buf_a: [0x0A; BUF_LEN],
+            buf_b: [0x0B; BUF_LEN],
+            len: UnsafeCell::new(BUF_LEN as u32),
+        }
+    }
+
+    fn read_len_volatile(&self) -> u32 {
+        unsafe { ptr::read_volatile(self.len.get()) }
+    }
+}
+
+/// Safe code that trusts `len` to describe `buf_b`.
+fn sum_b(frame: &MultiBufFrame) -> u64 {
+    let len = frame.read_len_volatile() as usize;
+    frame.buf_b[..len].iter().map(|&b| b as u64).sum()
+}
+
+/// Overflow one buffer by `OVERFLOW` bytes and report what it reached.
+fn overflow(buffer: &str, start: usize, value: u8, kind: &str) {
+    outln!("───────────────────────────────────────────────────────");
+    outln!("Overflow {buffer}: write {} x 0x{value:02X} at offset {start}", BUF_LEN + OVERFLOW);
+    outln!("───────────────────────────────────────────────────────");
+
+    let mut frame = MultiBufFrame::new();
+    let base_ptr: *mut u8 = (&mut frame as *mut MultiBufFrame).cast::<u8>();
+
+    let mut view: MemoryView<SIZE> = MemoryView::new(WATCHED, SEPS);
+    view.capture(base_ptr);
+    let initial = view.snapshot;
+    view.print_row("init");
+
+    // The overflow: no bounds check, so the last OVERFLOW bytes land in
+    // whatever comes next
+    unsafe {
+        for i in 0..BUF_LEN + OVERFLOW {
+            *base_ptr.add(start + i) = value;
+        }
+    }
+
+    view.capture(base_ptr);
+    view.print_diff(&initial, "after");
+
+    let hit: Vec<&str> = LAYOUT
+        .fields
+        .iter()
+        .filter(|f| f.offset != start && initial[f.offset..f.end()] != view.snapshot[f.offset..f.end()])
+        .map(|f| f.name)
+        .collect();
+    outln!("spilled into: {}  ({kind})", hit.join(", "));
+
+    match std::panic::catch_unwind(AssertUnwindSafe(|| sum_b(&frame))) {
+        Ok(sum) => outln!("sum_b() = {sum}: no crash, but buf_b now holds someone else's bytes"),
+        Err(_) => outln!(
+            "sum_b() PANICKED: len is now {}, so buf_b[..len] is out of bounds",
+            frame.read_len_volatile()
+        ),
+    }
+    outln!();
+}
+
+impl Scenario for MultiBuffer {
+    fn name(&self) -> &'static str {
+        "multi-buffer"
+    }
+
+    fn description(&self) -> &'static str {
+        "Overflowing into data vs overflowing into a control field"
+    }
+
+    fn run(&self, _opts: &Options) {
+        outln!("=======================================================");
+        outln!("   SCENARIO: which overflow reaches control data?");
+        outln!("=======================================================\n");
+
+        outln!("MultiBufFrame layout, tinted by field:");
+        let mut map: MemoryView<SIZE> = MemoryView::new(WATCHED, SEPS);
+        map.field_layout = Some(&LAYOUT);
+        let mut frame = MultiBufFrame::new();
+        map.capture((&mut frame as *mut MultiBufFrame).cast::<u8>());
+        map.print_row("fields");
+
+        let names: Vec<String> = LAYOUT
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| crate::color::field_label(i, f.name))
+            .collect();
+        outln!("{:<6}   {}\n", "", names.join(" "));
+
+        overflow("buf_a", BUF_A_OFF, 0xAA, "data-to-data: wrong values, same control flow");
+        overflow("buf_b", BUF_B_OFF, 0xBB, "data-to-control: the program's logic now lies");
+
+        outln!("Both are the same bug with the same overflow distance. Only the second");
+        outln!("one reaches a field that safe code uses to make decisions, and that's");
+        outln!("the one that turns a data glitch into a crash (or an exploit).");
+    }
+}
@@ -0,0 +1,118 @@
+//! Zero written into a `NonZeroU32`: corrupting a type with a niche.
+//!
+//! `NonZeroU32` promises it is never 0, and the compiler spends that promise:
+//! `Option<NonZeroU32>` uses 0 to mean `None` (so it stays 4 bytes), and
+//! dividing by a `NonZeroU32` skips the divide-by-zero check. An overflow that
+//! writes zeros over the field doesn't just change a number, it produces a
+//! value the type says can't exist.
+//!
+//! As with `--explain-unsafecell`, the actually-UB half is described, not run:
+//! reading the field as `NonZeroU32` once it holds 0 is UB, so anything it
+//! printed would prove nothing. The sound reinterpretations are run for real.
+
+use std::cell::UnsafeCell;
+use std::mem::{offset_of, size_of};
+use std::num::NonZeroU32;
+
+use super::Scenario;
+use crate::cli::Options;
+use crate::output::outln;
+use crate::MemoryView;
+
+pub struct NonzeroNiche;
+
+const BUF_LEN: usize = 4;
+
+/// A buffer followed by a field with a niche.
+#[repr(C)]
+struct NzFrame {
+    buffer: [u8; BUF_LEN],
+    nz: UnsafeCell<NonZeroU32>,
+}
+
+const SIZE: usize = size_of::<NzFrame>();
+const NZ_OFF: usize = offset_of!(NzFrame, nz);
+
+const WATCHED: &[(usize, usize)] = &[(NZ_OFF, NZ_OFF + 4)];
+const SEPS: &[usize] = &[NZ_OFF];
+
+/// The divisor `nz` starts out as
+const INITIAL: NonZeroU32 = NonZeroU32::new(0x0102_0304).unwrap();
+
+impl NzFrame {
+    fn new() -> Self {
+        Self {
+            buffer: [0xAA; BUF_LEN],
+            nz: UnsafeCell::new(INITIAL),
+        }
+    }
+
+    /// Read `nz` as the niche-optimized `Option` it is laid out like.
+    ///
+    /// Sound for any bit pattern: `Option<NonZeroU32>` is guaranteed to have
+    /// the same layout as `u32`, with 0 meaning `None`.
+    fn read_nz_as_option(&self) -> Option<NonZeroU32> {
+        unsafe { std::ptr::read_volatile(self.nz.get().cast::<Option<NonZeroU32>>()) }
+    }
+
+    /// Read the raw bits of `nz` as a plain `u32` (sound for any bit pattern).
+    fn read_nz_bits(&self) -> u32 {
+        unsafe { std::ptr::read_volatile(self.nz.get().cast::<u32>()) }
+    }
+}
+
+impl Scenario for NonzeroNiche {
+    fn name(&self) -> &'static str {
+        "nonzero-niche"
+    }
+
+    fn description(&self) -> &'static str {
+        "Zeros overflow into a NonZeroU32, breaking the niche the compiler relies on"
+    }
+
+    fn run(&self, opts: &Options) {
+        outln!("=======================================================");
+        outln!("   SCENARIO: zero written into a NonZeroU32");
+        outln!("=======================================================\n");
+
+        outln!("#[repr(C)] struct NzFrame {{ buffer: [u8; {BUF_LEN}], nz: UnsafeCell<NonZeroU32> }}");
+        outln!(
+            "size_of::<Option<NonZeroU32>>() = {}, size_of::<Option<u32>>() = {}: the 0 pattern is None\n",
+            size_of::<Option<NonZeroU32>>(),
+            size_of::<Option<u32>>()
+        );
+
+        let mut frame = NzFrame::new();
+        let base_ptr: *mut u8 = (&mut frame as *mut NzFrame).cast::<u8>();
+
+        let mut view: MemoryView<SIZE> = MemoryView::new(WATCHED, SEPS);
+        view.apply_display_options(opts);
+        view.capture(base_ptr);
+        let mut prev = view.snapshot;
+        view.print_row("init");
+        outln!("       nz = {} (as Option: {:?})", frame.read_nz_bits(), frame.read_nz_as_option());
+
+        // A zero-fill that runs one field too far, one byte at a time
+        for i in 0..SIZE {
+            unsafe { *base_ptr.add(i) = 0 };
+            view.capture(base_ptr);
+            view.print_diff(&prev, &format!("i={i}"));
+            prev = view.snapshot;
+        }
+
+        outln!();
+        outln!("Sound ways to look at the same 4 bytes now:");
+        outln!("  as u32:                 {}", frame.read_nz_bits());
+        outln!("  as Option<NonZeroU32>:  {:?}   (was Some({INITIAL}))", frame.read_nz_as_option());
+
+        outln!();
+        outln!("What safe code holding a NonZeroU32 would do (described, not run: it's UB):");
+        outln!("  let d: NonZeroU32 = unsafe {{ *frame.nz.get() }};  // reads a 0 NonZeroU32: UB");
+        outln!("  100 / d       // u32 / NonZeroU32 compiles with NO zero check; here it");
+        outln!("                // would divide by zero with nothing to catch it");
+        outln!("  Some(d)       // has the same bits as None: a Some that reads back as None");
+        outln!();
+        outln!("A niche is a promise the optimizer cashes in. Corrupt the bytes and the");
+        outln!("promise is broken everywhere the type was trusted, not just in this field.");
+    }
+}
@@ -0,0 +1,169 @@
+//! An overflow into a raw pointer field.
+//!
+//! Overwriting `len` makes safe code index out of bounds, which Rust catches.
+//! Overwriting a *pointer* is worse: the next dereference reads (or writes)
+//! wherever the attacker's bytes point, and no bounds check is involved at
+//! all. This is the classic "AAAA... -> 0x4141414141414141" crash.
+//!
+//! A segfault can't be caught with `catch_unwind`, so the dereference here is
+//! guarded: it only happens while the pointer still equals the address it was
+//! built with. Once the pointer has changed, the scenario reports the address
+//! that would have been dereferenced instead of dereferencing it.
+
+use std::cell::UnsafeCell;
+use std::mem::{align_of, offset_of, size_of};
+
+use super::Scenario;
+use crate::cli::Options;
+use crate::layout::{field, FieldInfo, Layout};
+use crate::output::outln;
+use crate::victim::Victim;
+use crate::MemoryView;
+
+pub struct PtrField;
+
+const BUF_LEN: usize = 8;
+
+/// A buffer followed by a pointer that safe code will follow.
+#[repr(C)]
+pub(crate) struct PtrFrame {
+    buffer: [u8; BUF_LEN],
+    ptr: UnsafeCell<*const u8>,
+}
+
+const LAYOUT: Layout = Layout {
+    name: "PtrFrame",
+    repr: "repr(C)",
+    size: size_of::<PtrFrame>(),
+    align: align_of::<PtrFrame>(),
+    fields: &[
+        field!(PtrFrame, buffer: [u8; BUF_LEN]),
+        field!(PtrFrame, ptr: *const u8),
+    ],
+};
+
+const SIZE: usize = size_of::<PtrFrame>();
+const PTR_OFF: usize = offset_of!(PtrFrame, ptr);
+const PTR_SZ: usize = size_of::<*const u8>();
+
+const WATCHED: &[(usize, usize)] = &[(PTR_OFF, PTR_OFF + PTR_SZ)];
+
+/// What `ptr` points at before the overflow
+static TARGET: u8 = 42;
+
+/// The byte every write stores: `'A'`, as in a stack of `AAAA...` input
+const FILL: u8 = b'A';
+
+impl PtrFrame {
+    fn new() -> Self {
+        Self {
+            buffer: [0; BUF_LEN],
+            ptr: UnsafeCell::new(&TARGET),
+        }
+    }
+
+    fn read_ptr_volatile(&self) -> *const u8 {
+        unsafe { std::ptr::read_volatile(self.ptr.get()) }
+    }
+}
+
+impl Victim for PtrFrame {
+    fn watched_ranges() -> &'static [(usize, usize)] {
+        WATCHED
+    }
+
+    fn describe_fields() -> &'static Layout {
+        &LAYOUT
+    }
+
+    fn buffer_offset() -> usize {
+        offset_of!(PtrFrame, buffer)
+    }
+
+    fn buffer_len() -> usize {
+        BUF_LEN
+    }
+}
+
+/// Safe code that trusts `ptr` to point at a live `u8`.
+///
+/// Sound only while nothing has written over `ptr`: the `unsafe` here relies
+/// on an invariant that the overflow breaks.
+fn read_target(frame: &PtrFrame) -> u8 {
+    unsafe { *frame.read_ptr_volatile() }
+}
+
+/// `read_target`, but only if `ptr` is still `expected`; otherwise the garbage
+/// address it would have dereferenced.
+fn read_target_guarded(frame: &PtrFrame, expected: *const u8) -> Result<u8, *const u8> {
+    let ptr = frame.read_ptr_volatile();
+    if ptr == expected { Ok(read_target(frame)) } else { Err(ptr) }
+}
+
+impl Scenario for PtrField {
+    fn name(&self) -> &'static str {
+        "ptr"
+    }
+
+    fn description(&self) -> &'static str {
+        "The overflow marches into a raw pointer that safe code then dereferences"
+    }
+
+    fn run(&self, opts: &Options) {
+        outln!("=======================================================");
+        outln!("   SCENARIO: overflowing into a pointer field");
+        outln!("=======================================================\n");
+
+        outln!("#[repr(C)] struct PtrFrame {{ buffer: [u8; {BUF_LEN}], ptr: UnsafeCell<*const u8> }}");
+        outln!("ptr starts out pointing at a static u8 holding {TARGET}\n");
+
+        let mut frame = PtrFrame::new();
+        let original = frame.read_ptr_volatile();
+        let base_ptr = frame.base_ptr();
+
+        let mut view: MemoryView<SIZE> = MemoryView::for_victim::<PtrFrame>();
+        view.apply_display_options(opts);
+        view.capture(base_ptr);
+        let mut prev = view.snapshot;
+        view.print_row("init");
+        outln!("       ptr = {original:p}, *ptr = {}", read_target(&frame));
+
+        // Copy "AAAA..." with no bounds check, one byte at a time, until the
+        // whole pointer has been overwritten
+        for i in 0..SIZE {
+            unsafe { *base_ptr.add(PtrFrame::buffer_offset() + i) = FILL };
+            view.capture(base_ptr);
+            view.print_diff(&prev, &format!("i={i}"));
+            prev = view.snapshot;
+        }
+
+        outln!();
+        match read_target_guarded(&frame, original) {
+            Ok(value) => outln!("read_target() = {value} (ptr was not touched)"),
+            Err(garbage) => {
+                outln!("ptr is now {garbage:p} (was {original:p})");
+                outln!("read_target() would dereference garbage pointer {garbage:p}: skipped.");
+                outln!("Unguarded, that's a segfault at best. At worst the address is mapped,");
+                outln!("and the program quietly reads (or writes) whatever the attacker chose.");
+            }
+        }
+        outln!();
+        outln!("No bounds check is involved: a raw pointer carries no length to check against.");
+        outln!("Corrupt a length and Rust panics; corrupt a pointer and it trusts you.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guarded_read_refuses_a_changed_pointer() {
+        let frame = PtrFrame::new();
+        let original = frame.read_ptr_volatile();
+        assert_eq!(read_target_guarded(&frame, original), Ok(TARGET));
+
+        unsafe { *frame.ptr.get() = 0x4141_4141_usize as *const u8 };
+        assert_eq!(read_target_guarded(&frame, original), Err(0x4141_4141_usize as *const u8));
+    }
+}
@@ -0,0 +1,88 @@
+//! `Frame` next to `ReorderFrame`: the same fields, with and without `repr(C)`.
+//!
+//! The overflow only reaches `len` because `repr(C)` lays fields out in
+//! declaration order, so the bytes after `buffer` are guaranteed to be `len`.
+//! The default representation makes no such promise: rustc is free to reorder
+//! fields (in practice it sorts them by alignment), and the order can change
+//! between compiler versions or when a field is added. This scenario prints
+//! both layouts from `offset_of!` and says what the bytes past `buffer` would
+//! hit in each.
+
+use super::Scenario;
+use crate::cli::Options;
+use crate::layout::{self, Layout};
+use crate::output::outln;
+
+pub struct ReprRust;
+
+/// What a write running off the end of `buffer` reaches first, skipping padding.
+fn after_buffer(layout: &Layout) -> String {
+    let Some(buffer) = layout.field("buffer") else {
+        return "nothing (no buffer field)".to_string();
+    };
+    let next = layout.fields_by_offset().into_iter().find(|f| f.offset >= buffer.end());
+    let (target, start) = match &next {
+        Some(field) => (format!("`{}`", field.name), field.offset),
+        None => ("whatever lies past the end of the struct".to_string(), layout.size),
+    };
+    match start - buffer.end() {
+        0 => target,
+        gap => format!("{target}, after {gap} bytes of padding"),
+    }
+}
+
+impl Scenario for ReprRust {
+    fn name(&self) -> &'static str {
+        "repr-rust"
+    }
+
+    fn description(&self) -> &'static str {
+        "Frame's fields without repr(C): offset_of! shows the compiler reordering them"
+    }
+
+    fn run(&self, _opts: &Options) {
+        let (c, rust) = (&layout::FRAME, &layout::REORDER_FRAME);
+
+        outln!("=======================================================");
+        outln!("   SCENARIO: repr(Rust) layout is not declaration order");
+        outln!("=======================================================\n");
+
+        outln!("Both structs declare buffer, len, num, guard, in that order.");
+        outln!("Offsets below come from offset_of! on this build:\n");
+
+        let header = |name: &str, layout: &Layout| format!("{name} ({})", layout.repr);
+        outln!("  {:<8} {:<20} {}", "field", header(c.name, c), header(rust.name, rust));
+        outln!("  {:<8} {:<20} {}", "-----", "-------", "-------");
+        for (c_field, rust_field) in c.fields.iter().zip(rust.fields) {
+            let range = |offset: usize, end: usize| format!("[{offset}..{end})");
+            let moved = if c_field.offset == rust_field.offset { "" } else { "<- moved" };
+            let row = format!(
+                "  {:<8} {:<20} {:<10} {moved}",
+                c_field.name,
+                range(c_field.offset, c_field.end()),
+                range(rust_field.offset, rust_field.end())
+            );
+            outln!("{}", row.trim_end());
+        }
+        outln!("  {:<8} {:<20} {}", "size", c.size, rust.size);
+        outln!();
+
+        outln!("Writing past buffer reaches:");
+        outln!("  {:<10} {}", c.name, after_buffer(c));
+        outln!("  {:<10} {}\n", rust.name, after_buffer(rust));
+
+        if c.fields.iter().zip(rust.fields).any(|(a, b)| a.offset != b.offset) {
+            let order: Vec<String> = rust.fields_by_offset().iter().map(|f| f.name.to_string()).collect();
+            outln!("NOTE: the offsets differ. Without repr(C), this compiler stored the fields");
+            outln!("as {}, not in the order they were declared.\n", order.join(", "));
+        } else {
+            outln!("This build happened to keep declaration order, but repr(Rust) doesn't");
+            outln!("promise it: another compiler version, or one more field, can change it.\n");
+        }
+
+        outln!("The demo's overflow only corrupts len because Frame is repr(C). Code that");
+        outln!("computes offsets by hand on a default-repr struct, or assumes which field");
+        outln!("sits next to which, is relying on a layout nobody promised: that's a bug");
+        outln!("even before any byte is written out of bounds. Use repr(C) and offset_of!.");
+    }
+}
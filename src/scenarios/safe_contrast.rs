@@ -0,0 +1,104 @@
+//! Every raw write of the overflow, shadowed by the checked write safe Rust would make.
+//!
+//! Before each unchecked `*buf_ptr.add(i) = value`, the same byte is offered
+//! to `frame.buffer.get_mut(i)`. Inside the buffer that returns `Some` and the
+//! safe write lands exactly where the raw one does; from index `BUFFER_SIZE`
+//! on it returns `None`, and safe code would have stopped there. The raw write
+//! goes ahead regardless, so the dump still corrupts: the annotation under
+//! each row shows the guard that the unsafe loop is missing.
+
+use std::panic::AssertUnwindSafe;
+
+use super::Scenario;
+use crate::cli::Options;
+use crate::output::outln;
+use crate::victim::Victim;
+use crate::{
+    color, safe_sum_prefix, Frame, MemoryView, BUFFER_SIZE, BUF_OFF, FRAME_SIZE, LEN_OFF, LEN_SZ,
+};
+
+pub struct SafeContrast;
+
+/// Far enough to rewrite every byte of `len`
+const WRITE_LEN: usize = LEN_OFF + LEN_SZ - BUF_OFF;
+
+impl Scenario for SafeContrast {
+    fn name(&self) -> &'static str {
+        "safe-contrast"
+    }
+
+    fn description(&self) -> &'static str {
+        "Each raw write next to buffer.get_mut(i): where safe Rust's bounds check would have fired"
+    }
+
+    fn run(&self, opts: &Options) {
+        outln!("=======================================================");
+        outln!("   SCENARIO: what safe Rust would have done");
+        outln!("=======================================================\n");
+
+        outln!("Each write below happens twice: once as frame.buffer.get_mut(i) (checked),");
+        outln!("and once through a raw pointer (unchecked). Only the raw one ignores the");
+        outln!("answer.\n");
+
+        let mut frame = Frame::new();
+        frame.zero_padding();
+        let mut view: MemoryView<FRAME_SIZE> = MemoryView::for_victim::<Frame>();
+        view.apply_display_options(opts);
+        view.capture((&mut frame as *mut Frame).cast::<u8>());
+        view.print_row("init");
+
+        let mut first_rejected = None;
+        for i in 0..WRITE_LEN {
+            let value = opts.fill.byte_at(i, None);
+
+            // The checked write: `Some` means the index is in bounds and the byte is stored
+            let allowed = match frame.buffer.get_mut(i) {
+                Some(slot) => {
+                    *slot = value;
+                    true
+                }
+                None => false,
+            };
+
+            // The unchecked write, which doesn't ask. The pointer is taken fresh
+            // after the safe borrow above has ended.
+            let base_ptr: *mut u8 = (&mut frame as *mut Frame).cast::<u8>();
+            unsafe { *base_ptr.add(BUF_OFF + i) = value };
+
+            let prev = view.snapshot;
+            view.capture(base_ptr);
+            view.print_diff(&prev, &format!("i={i}"));
+
+            if allowed {
+                outln!("       safe: buffer.get_mut({i}) = Some -> written");
+            } else {
+                let note = format!("safe: buffer.get_mut({i}) = None -> rejected at index {i}");
+                if first_rejected.is_none() {
+                    first_rejected = Some(i);
+                    outln!("       {}  <- the bounds check fires here", color::changed_text(&note));
+                } else {
+                    outln!("       {note}");
+                }
+            }
+        }
+
+        outln!();
+        outln!(
+            "After:  len={}, num={}, guard=0x{:08X}",
+            frame.read_len_volatile(),
+            frame.read_num_volatile(),
+            frame.read_guard_volatile()
+        );
+        match std::panic::catch_unwind(AssertUnwindSafe(|| safe_sum_prefix(&frame))) {
+            Ok(sum) => outln!("safe_sum_prefix() = {sum} (len was still valid)"),
+            Err(_) => outln!("safe_sum_prefix() PANICKED! (len was corrupted to > {BUFFER_SIZE})"),
+        }
+
+        outln!();
+        if let Some(index) = first_rejected {
+            outln!("Safe Rust would have written {index} of the {WRITE_LEN} bytes and refused the rest:");
+            outln!("get_mut({index}) is None because buffer holds {BUFFER_SIZE}. Every row from i={index} on is a");
+            outln!("write that only happened because the raw pointer never asked.");
+        }
+    }
+}
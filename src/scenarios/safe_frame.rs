@@ -0,0 +1,90 @@
+//! The same overflowing byte sequence, given to `Frame` and to `SafeFrame`.
+//!
+//! Raw pointer writes into `Frame` corrupt `len` and the fields after it.
+//! `SafeFrame::push` refuses the byte after `BUFFER_SIZE` with `Err(Full)`,
+//! and everything after the buffer stays exactly as it was. Same bytes, same
+//! memory layout: the only difference is whether the invariant lives inside
+//! the type.
+
+use std::panic::AssertUnwindSafe;
+
+use super::Scenario;
+use crate::cli::Options;
+use crate::output::outln;
+use crate::safe_frame::SafeFrame;
+use crate::victim::Victim;
+use crate::{safe_sum_prefix, Frame, MemoryView, BUFFER_SIZE, BUF_OFF, FRAME_SIZE};
+
+pub struct SafeFrameScenario;
+
+/// How many bytes both versions try to store
+const WRITE_LEN: usize = 10;
+
+impl Scenario for SafeFrameScenario {
+    fn name(&self) -> &'static str {
+        "safe-frame"
+    }
+
+    fn description(&self) -> &'static str {
+        "The same overflowing bytes, refused by a bounds-checked SafeFrame API"
+    }
+
+    fn run(&self, opts: &Options) {
+        outln!("=======================================================");
+        outln!("   SCENARIO: raw Frame vs SafeFrame, {WRITE_LEN} bytes each");
+        outln!("=======================================================\n");
+
+        // --------------------------------------------------------------------
+        // Raw access: nothing stops the writes
+        // --------------------------------------------------------------------
+        let mut frame = Frame::new();
+        frame.zero_padding();
+        let base_ptr: *mut u8 = (&mut frame as *mut Frame).cast::<u8>();
+
+        let mut view: MemoryView<FRAME_SIZE> = MemoryView::for_victim::<Frame>();
+        view.apply_display_options(opts);
+        view.capture(base_ptr);
+        let prev = view.snapshot;
+
+        outln!("Frame, raw pointer writes:");
+        view.print_row("init");
+        for i in 0..WRITE_LEN {
+            unsafe { *base_ptr.add(BUF_OFF + i) = i as u8 };
+        }
+        view.capture(base_ptr);
+        view.print_diff(&prev, "raw");
+
+        match std::panic::catch_unwind(AssertUnwindSafe(|| safe_sum_prefix(&frame))) {
+            Ok(sum) => outln!("safe_sum_prefix() = {sum}"),
+            Err(_) => outln!("safe_sum_prefix() PANICKED (len is {})", frame.read_len_volatile()),
+        }
+        outln!();
+
+        // --------------------------------------------------------------------
+        // Encapsulated: push() is the only way in, and it checks
+        // --------------------------------------------------------------------
+        let mut safe = SafeFrame::new();
+        safe.zero_padding();
+        let mut view: MemoryView<FRAME_SIZE> = MemoryView::for_victim::<Frame>();
+        view.apply_display_options(opts);
+        view.capture((safe.as_frame() as *const Frame).cast::<u8>());
+        let prev = view.snapshot;
+
+        outln!("SafeFrame, push():");
+        view.print_row("init");
+        for i in 0..WRITE_LEN {
+            match safe.push(i as u8) {
+                Ok(()) => outln!("  push({i}) -> Ok, len = {}", safe.len()),
+                Err(err) => outln!("  push({i}) -> Err({err:?}): {err}"),
+            }
+        }
+        view.capture((safe.as_frame() as *const Frame).cast::<u8>());
+        view.print_diff(&prev, "safe");
+        outln!("sum_prefix() = {} (cannot panic: len <= {BUFFER_SIZE} by construction)", safe.sum_prefix());
+
+        outln!();
+        outln!("Only the buffer and len changed: len counted up to {BUFFER_SIZE} and stopped, and");
+        outln!("num and guard were never touched. Put the invariant behind a small API");
+        outln!("and safe callers can't break it, no matter what they pass in.");
+    }
+}
@@ -0,0 +1,96 @@
+//! An overflow that corrupts `num` and nothing else, so nothing panics.
+//!
+//! The default demo always reaches `len`, and `safe_sum_prefix` panicking is
+//! what makes the damage visible. Here the stray write skips over `len`: a
+//! 4-byte checksum is stored at `buffer[12..16]`, an index computed for a
+//! 16-byte buffer, which on this `Frame` is exactly `num`. `len` is untouched,
+//! the safe code runs to completion with the right answer, and the only sign
+//! of trouble is a value nobody happened to check.
+
+use std::mem::size_of;
+use std::panic::AssertUnwindSafe;
+
+use super::Scenario;
+use crate::cli::Options;
+use crate::output::outln;
+use crate::victim::Victim;
+use crate::{safe_sum_prefix, Frame, MemoryView, BUFFER_SIZE, BUF_OFF, FRAME_SIZE, NUM_OFF};
+
+pub struct Silent;
+
+/// Where the checksum goes, as an index into `buffer`: right on top of `num`
+const CHECKSUM_INDEX: usize = NUM_OFF - BUF_OFF;
+
+/// The value the buggy store writes
+const CHECKSUM: u32 = 0x0BAD_F00D;
+
+const _: () = assert!(CHECKSUM_INDEX >= BUFFER_SIZE);
+
+impl Scenario for Silent {
+    fn name(&self) -> &'static str {
+        "silent"
+    }
+
+    fn description(&self) -> &'static str {
+        "A write past the buffer that skips len and lands on num: no panic, still corrupted"
+    }
+
+    fn run(&self, opts: &Options) {
+        outln!("=======================================================");
+        outln!("   SCENARIO: silent corruption (no panic, still broken)");
+        outln!("=======================================================\n");
+
+        let mut frame = Frame::new();
+        // Real data in the buffer, written through safe indexing, so the sum means something
+        for (i, slot) in frame.buffer.iter_mut().enumerate() {
+            *slot = i as u8 + 1;
+        }
+        frame.zero_padding();
+        let base_ptr: *mut u8 = (&mut frame as *mut Frame).cast::<u8>();
+
+        let mut view: MemoryView<FRAME_SIZE> = MemoryView::for_victim::<Frame>();
+        view.apply_display_options(opts);
+        view.capture(base_ptr);
+        let mut prev = view.snapshot;
+
+        let num_before = frame.read_num_volatile();
+        outln!(
+            "Before: len={}, num={num_before}, guard=0x{:08X}",
+            frame.read_len_volatile(),
+            frame.read_guard_volatile()
+        );
+        outln!("safe_sum_prefix() = {}", safe_sum_prefix(&frame));
+        outln!();
+
+        // The bug: the index is right for a 16-byte buffer, not this one
+        let end = CHECKSUM_INDEX + size_of::<u32>();
+        outln!("Store checksum 0x{CHECKSUM:08X} at buffer[{CHECKSUM_INDEX}..{end}]   // buffer holds {BUFFER_SIZE}");
+        view.print_row("init");
+        for (k, byte) in CHECKSUM.to_le_bytes().into_iter().enumerate() {
+            unsafe { *base_ptr.add(BUF_OFF + CHECKSUM_INDEX + k) = byte };
+            view.capture(base_ptr);
+            view.print_diff(&prev, &format!("i={}", CHECKSUM_INDEX + k));
+            prev = view.snapshot;
+        }
+
+        outln!();
+        outln!(
+            "After:  len={}, num={}, guard=0x{:08X}",
+            frame.read_len_volatile(),
+            frame.read_num_volatile(),
+            frame.read_guard_volatile()
+        );
+        match std::panic::catch_unwind(AssertUnwindSafe(|| safe_sum_prefix(&frame))) {
+            Ok(sum) => outln!("safe_sum_prefix() = {sum} (same answer as before, no panic)"),
+            Err(_) => outln!("safe_sum_prefix() PANICKED! (len was corrupted to > {BUFFER_SIZE})"),
+        }
+        outln!("read_num_volatile() = {} (was {num_before})", frame.read_num_volatile());
+
+        outln!();
+        outln!("No panic does not mean no corruption. len still holds {BUFFER_SIZE}, so the");
+        outln!("bounds check passes and safe_sum_prefix returns the right sum, while num");
+        outln!("now holds garbage that whatever reads it next will trust. The crash in the");
+        outln!("default demo is the lucky case: it tells you something went wrong. An");
+        outln!("overflow that misses every checked value just leaves wrong data behind.");
+    }
+}
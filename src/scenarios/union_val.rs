@@ -0,0 +1,88 @@
+//! A `repr(C)` union: the sanctioned way to give the same bytes several types.
+//!
+//! The rest of the demo reinterprets bytes *by accident*, through an overflow.
+//! A union does it on purpose: every field starts at offset 0, so writing
+//! `.bytes` and reading `.i` or `.f` looks at the same four bytes. Reading a
+//! field other than the one last written is `unsafe`, and it's only sound when
+//! every bit pattern is a valid value of the field you read. `i32`, `f32`, and
+//! `[u8; 4]` all qualify; `bool`, `char`, references, and enums do not.
+
+use std::mem::size_of;
+
+use super::Scenario;
+use crate::cli::Options;
+use crate::output::outln;
+use crate::MemoryView;
+
+pub struct UnionVal;
+
+/// Four bytes, three interpretations.
+#[repr(C)]
+union Val {
+    i: i32,
+    f: f32,
+    bytes: [u8; 4],
+}
+
+const SIZE: usize = size_of::<Val>();
+
+/// Every byte belongs to every field, so there is nothing to separate
+const WATCHED: &[(usize, usize)] = &[(0, SIZE)];
+const SEPS: &[usize] = &[];
+
+/// Byte patterns written through `.bytes`, with what makes each interesting
+const PATTERNS: &[([u8; 4], &str)] = &[
+    ([0x00, 0x00, 0x80, 0x3F], "1.0 as f32, but a large i32"),
+    ([0xDB, 0x0F, 0x49, 0x40], "pi as f32"),
+    ([0x41, 0x41, 0x41, 0x41], "\"AAAA\" from a text overflow, read back as 12.08"),
+    ([0xFF, 0xFF, 0xFF, 0xFF], "-1 as i32, NaN as f32"),
+];
+
+impl Scenario for UnionVal {
+    fn name(&self) -> &'static str {
+        "union-val"
+    }
+
+    fn description(&self) -> &'static str {
+        "One repr(C) union read as bytes, i32, and f32"
+    }
+
+    fn run(&self, opts: &Options) {
+        outln!("=======================================================");
+        outln!("   SCENARIO: one union, three interpretations");
+        outln!("=======================================================\n");
+
+        outln!("#[repr(C)] union Val {{ i: i32, f: f32, bytes: [u8; 4] }}   // {SIZE} bytes, all fields at offset 0\n");
+
+        let mut val = Val { i: 0 };
+
+        let mut view: MemoryView<SIZE> = MemoryView::new(WATCHED, SEPS);
+        view.apply_display_options(opts);
+        view.capture((&val as *const Val).cast::<u8>());
+        view.print_row("init");
+
+        for &(pattern, note) in PATTERNS {
+            let prev = view.snapshot;
+
+            // Writing a union field is safe: it just overwrites the bytes
+            val.bytes = pattern;
+
+            view.capture((&val as *const Val).cast::<u8>());
+            view.print_diff(&prev, "write");
+
+            // SAFETY: every bit pattern is a valid i32 and a valid f32, so
+            // reading a field other than the one just written is sound here
+            let (i, f) = unsafe { (val.i, val.f) };
+            outln!("       .bytes = {pattern:02X?}");
+            outln!("       .i     = {i}");
+            outln!("       .f     = {f}");
+            outln!("       ({note})\n");
+        }
+
+        outln!("Reading .i or .f after writing .bytes needs `unsafe`, but it's sound:");
+        outln!("no 4-byte pattern is an invalid i32 or f32. Add `b: bool` to the union");
+        outln!("and reading .b after writing 0x41 would be UB, because only 0x00 and");
+        outln!("0x01 are valid bools. A union moves the reinterpretation into the open;");
+        outln!("it doesn't make every reinterpretation legal.");
+    }
+}
@@ -0,0 +1,104 @@
+//! Reading a `Frame` through a pointer that outlived its `Box`.
+//!
+//! Use-after-free is the other classic memory bug: nothing is written out of
+//! bounds, the memory simply isn't yours any more. The read below "works":
+//! no crash, no panic, a plausible-looking dump. That is the lesson. The
+//! bytes are whatever the allocator left there, and none of them mean
+//! anything.
+
+use super::Scenario;
+use crate::cli::Options;
+use crate::output::outln;
+use crate::{Frame, MemoryView, FRAME_SIZE};
+
+pub struct UseAfterFree;
+
+/// Fill byte for the allocation that may reuse the freed block
+const REUSE_FILL: u8 = 0x5A;
+
+impl Scenario for UseAfterFree {
+    fn name(&self) -> &'static str {
+        "uaf"
+    }
+
+    fn description(&self) -> &'static str {
+        "A Frame read through a stale pointer after its Box was dropped"
+    }
+
+    /// Drop a `Box<Frame>`, then keep reading through a raw pointer to it.
+    ///
+    /// **This is undefined behavior, and it is run for real.** Every read
+    /// after `drop` goes through a dangling pointer, so the compiler and the
+    /// allocator owe us nothing: the bytes printed are implementation-defined
+    /// at best (they depend on the allocator, the build, and the phase of the
+    /// moon) and the program would be within its rights to crash. With the
+    /// system allocator the read quietly succeeds, which is exactly why this
+    /// bug is so hard to spot.
+    fn run(&self, opts: &Options) {
+        outln!("=======================================================");
+        outln!("   SCENARIO: use after free");
+        outln!("=======================================================\n");
+
+        let boxed = Box::new(Frame::new());
+        let stale: *const Frame = &*boxed;
+        let base_ptr = stale.cast::<u8>();
+
+        let mut view: MemoryView<FRAME_SIZE> = MemoryView::for_victim::<Frame>();
+        view.apply_display_options(opts);
+        view.capture(base_ptr);
+        let live = view.snapshot;
+
+        outln!("let boxed = Box::new(Frame::new());   // heap block at {stale:p}");
+        outln!("let stale: *const Frame = &*boxed;    // raw pointers don't keep it alive");
+        view.print_row("live");
+        outln!(
+            "       len={}, num={}, guard=0x{:08X}",
+            boxed.read_len_volatile(),
+            boxed.read_num_volatile(),
+            boxed.read_guard_volatile()
+        );
+
+        outln!();
+        outln!("drop(boxed);                          // the block goes back to the allocator");
+        drop(boxed);
+
+        // UB from here on: every read goes through a dangling pointer. Take
+        // the readings before printing anything, since printing allocates
+        // and would hand the freed block to someone else first.
+        let frame = unsafe { &*stale };
+        view.capture(base_ptr);
+        let freed = view.snapshot;
+        let freed_fields = (frame.read_len_volatile(), frame.read_num_volatile(), frame.read_guard_volatile());
+
+        // A same-sized allocation is the likeliest candidate for the freed block
+        let reuse = Box::new([REUSE_FILL; FRAME_SIZE]);
+        let reused = std::ptr::eq(reuse.as_ptr(), base_ptr);
+        view.capture(base_ptr);
+        let after_reuse = view.snapshot;
+        let reuse_fields = (frame.read_len_volatile(), frame.read_num_volatile(), frame.read_guard_volatile());
+
+        view.snapshot = freed;
+        view.print_diff(&live, "freed");
+        let (len, num, guard) = freed_fields;
+        outln!("       len={len}, num={num}, guard=0x{guard:08X}   (read through the stale pointer)");
+
+        outln!();
+        outln!("let reuse = Box::new([0x{REUSE_FILL:02X}; {FRAME_SIZE}]);   // heap block at {:p}", reuse.as_ptr());
+        view.snapshot = after_reuse;
+        view.print_diff(&freed, "reused");
+        let (len, num, guard) = reuse_fields;
+        outln!("       len={len}, num={num}, guard=0x{guard:08X}   (same stale pointer)");
+        drop(reuse);
+
+        outln!();
+        if reused {
+            outln!("The allocator handed the freed block straight to the next Box: the stale");
+            outln!("pointer now reads someone else's data as if it were a Frame.");
+        } else {
+            outln!("This time the next Box landed elsewhere, but nothing promised that: the");
+            outln!("stale pointer reads whatever the allocator keeps in the freed block.");
+        }
+        outln!("No crash and no panic: every read \"succeeded\", and none of it means anything.");
+        outln!("The bytes above are not the Frame's values; they're an accident of the allocator.");
+    }
+}
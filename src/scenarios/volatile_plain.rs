@@ -0,0 +1,86 @@
+//! `read_len_volatile` next to an ordinary read of the same field.
+//!
+//! Every reader in the demo is volatile so that the dump shows what is in
+//! memory, not what the compiler remembers. This scenario reads `len` both
+//! ways after each write of an overflow and prints the two side by side.
+//!
+//! What it shows depends on the build. At `opt-level = 0` (this crate's
+//! default, even for `--release`) nothing is cached, so the columns always
+//! match. With optimizations on, a plain read may be served from a register
+//! whenever the compiler can prove nothing wrote `len` in between; here the
+//! raw pointer writes are visible to it, so it usually has to reload anyway.
+//! The column that *can't* go stale is the volatile one: that guarantee is
+//! the point, whatever a given build happens to do.
+
+use super::Scenario;
+use crate::cli::Options;
+use crate::output::outln;
+use crate::victim::Victim;
+use crate::{Frame, MemoryView, BUF_OFF, FRAME_SIZE, LEN_OFF, LEN_SZ};
+
+pub struct VolatileVsPlain;
+
+/// Far enough to rewrite every byte of `len`
+const WRITE_LEN: usize = LEN_OFF + LEN_SZ - BUF_OFF;
+
+impl Scenario for VolatileVsPlain {
+    fn name(&self) -> &'static str {
+        "volatile-vs-plain"
+    }
+
+    fn description(&self) -> &'static str {
+        "len read with read_volatile and with a plain load after every write, side by side"
+    }
+
+    fn run(&self, opts: &Options) {
+        outln!("=======================================================");
+        outln!("   SCENARIO: read_volatile vs a plain read");
+        outln!("=======================================================\n");
+
+        let optimized = if cfg!(debug_assertions) { "a debug build" } else { "a build without debug assertions" };
+        outln!("This is {optimized}. At opt-level 0 (the crate's default) the two columns");
+        outln!("always agree; rebuild with optimizations to give the plain read a chance");
+        outln!("to go stale:\n");
+        outln!("  CARGO_PROFILE_RELEASE_OPT_LEVEL=3 cargo run --release -- --scenario volatile-vs-plain\n");
+
+        let mut frame = Frame::new();
+        frame.zero_padding();
+        let base_ptr: *mut u8 = (&mut frame as *mut Frame).cast::<u8>();
+
+        let mut view: MemoryView<FRAME_SIZE> = MemoryView::for_victim::<Frame>();
+        view.apply_display_options(opts);
+        view.capture(base_ptr);
+        let initial = view.snapshot;
+        view.print_row("init");
+
+        outln!();
+        outln!("  {:<5} {:>12} {:>12}", "write", "volatile", "plain");
+        let mut stale = 0;
+        for i in 0..WRITE_LEN {
+            unsafe { *base_ptr.add(BUF_OFF + i) = i as u8 };
+            let volatile = frame.read_len_volatile();
+            let plain = frame.read_len_plain();
+            let note = if volatile == plain {
+                ""
+            } else {
+                stale += 1;
+                "  <- plain read is stale"
+            };
+            outln!("  {:<5} {volatile:>12} {plain:>12}{note}", format!("i={i}"));
+        }
+
+        outln!();
+        view.capture(base_ptr);
+        view.print_diff(&initial, "after");
+
+        outln!();
+        if stale == 0 {
+            outln!("Both reads agreed every time in this build. That's allowed, not promised:");
+            outln!("only read_volatile guarantees a load from memory on every call.");
+        } else {
+            outln!("The plain read missed {stale} write(s): the optimizer reused a value it");
+            outln!("had already loaded. read_volatile can't do that, which is why the demo");
+            outln!("uses it for every field it shows.");
+        }
+    }
+}
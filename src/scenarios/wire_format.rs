@@ -0,0 +1,171 @@
+//! The same corrupted bytes, handed to a validating parser and a trusting one.
+//!
+//! Treat `Frame` as a wire format: the bytes could just as well have arrived
+//! over a socket. A parser that checks `len <= BUFFER_SIZE` and the guard
+//! sentinel rejects the corrupted message with an error. A parser that takes
+//! `len` at its word panics, exactly like `safe_sum_prefix`. Validating at the
+//! boundary is what turns "attacker-controlled length" into "bad input".
+
+use std::fmt;
+use std::panic::AssertUnwindSafe;
+
+use super::Scenario;
+use crate::cli::Options;
+use crate::output::outln;
+use crate::victim::Victim;
+use crate::{Frame, MemoryView, BUFFER_SIZE, BUF_OFF, FRAME_SIZE, GUARD_OFF, LEN_OFF, NUM_OFF};
+
+pub struct WireFormat;
+
+/// The guard value every well-formed message carries
+const SENTINEL: u32 = 0xDEAD_BEEF;
+
+/// How many ramp bytes the buggy writer puts into the 5-byte buffer
+const WRITE_LEN: usize = 10;
+
+/// Why a message was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WireError {
+    /// `len` claims more payload than the buffer can hold
+    LenOutOfBounds(u32),
+    /// The guard isn't the sentinel, so the message was damaged
+    GuardMismatch(u32),
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WireError::LenOutOfBounds(len) => write!(f, "len {len} exceeds the {BUFFER_SIZE}-byte buffer"),
+            WireError::GuardMismatch(guard) => write!(f, "guard 0x{guard:08X} is not 0x{SENTINEL:08X}"),
+        }
+    }
+}
+
+/// A message that passed validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message<'a> {
+    pub payload: &'a [u8],
+    pub num: i32,
+}
+
+/// Encode `frame` field by field. Padding is written as zeros.
+pub fn serialize(frame: &Frame) -> [u8; FRAME_SIZE] {
+    let mut bytes = [0u8; FRAME_SIZE];
+    bytes[BUF_OFF..BUF_OFF + BUFFER_SIZE].copy_from_slice(&frame.buffer);
+    bytes[LEN_OFF..LEN_OFF + 4].copy_from_slice(&frame.read_len_volatile().to_le_bytes());
+    bytes[NUM_OFF..NUM_OFF + 4].copy_from_slice(&frame.read_num_volatile().to_le_bytes());
+    bytes[GUARD_OFF..GUARD_OFF + 4].copy_from_slice(&frame.read_guard_volatile().to_le_bytes());
+    bytes
+}
+
+fn read_u32(bytes: &[u8; FRAME_SIZE], offset: usize) -> u32 {
+    u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+}
+
+/// Parse a message, refusing anything that breaks the format's invariants.
+pub fn deserialize(bytes: &[u8; FRAME_SIZE]) -> Result<Message<'_>, WireError> {
+    let len = read_u32(bytes, LEN_OFF);
+    if len as usize > BUFFER_SIZE {
+        return Err(WireError::LenOutOfBounds(len));
+    }
+    let guard = read_u32(bytes, GUARD_OFF);
+    if guard != SENTINEL {
+        return Err(WireError::GuardMismatch(guard));
+    }
+
+    Ok(Message {
+        payload: &bytes[BUF_OFF..BUF_OFF + len as usize],
+        num: read_u32(bytes, NUM_OFF) as i32,
+    })
+}
+
+/// Parse a message the naive way: whatever `len` says, goes.
+///
+/// Still 100% safe Rust, so the worst it can do is panic; in C the same code
+/// would read past the buffer instead.
+pub fn deserialize_trusting(bytes: &[u8; FRAME_SIZE]) -> Message<'_> {
+    let len = read_u32(bytes, LEN_OFF) as usize;
+    let buffer = &bytes[BUF_OFF..BUF_OFF + BUFFER_SIZE];
+    Message {
+        // PANICS if len > BUFFER_SIZE
+        payload: &buffer[..len],
+        num: read_u32(bytes, NUM_OFF) as i32,
+    }
+}
+
+impl Scenario for WireFormat {
+    fn name(&self) -> &'static str {
+        "wire-format"
+    }
+
+    fn description(&self) -> &'static str {
+        "A validating parser rejects corrupted bytes that crash a trusting one"
+    }
+
+    fn run(&self, opts: &Options) {
+        outln!("=======================================================");
+        outln!("   SCENARIO: validating vs trusting deserialization");
+        outln!("=======================================================\n");
+
+        let mut frame = Frame::new();
+        frame.zero_padding();
+        let base_ptr: *mut u8 = (&mut frame as *mut Frame).cast::<u8>();
+
+        let mut view: MemoryView<FRAME_SIZE> = MemoryView::for_victim::<Frame>();
+        view.apply_display_options(opts);
+        view.capture(base_ptr);
+        let prev = view.snapshot;
+        view.print_row("init");
+
+        // The sender's bug: a WRITE_LEN-byte ramp into the BUFFER_SIZE-byte buffer
+        for i in 0..WRITE_LEN {
+            unsafe { *base_ptr.add(BUF_OFF + i) = i as u8 };
+        }
+        view.capture(base_ptr);
+        view.print_diff(&prev, "sent");
+
+        let wire = serialize(&frame);
+        outln!("On the wire: {} bytes, len field = {}\n", wire.len(), read_u32(&wire, LEN_OFF));
+
+        outln!("Validating parser:");
+        match deserialize(&wire) {
+            Ok(message) => outln!("  accepted: payload {:02X?}, num {}", message.payload, message.num),
+            Err(err) => outln!("  rejected: {err}"),
+        }
+
+        outln!("Trusting parser:");
+        match std::panic::catch_unwind(AssertUnwindSafe(|| deserialize_trusting(&wire).payload.len())) {
+            Ok(len) => outln!("  accepted a {len}-byte payload"),
+            Err(_) => outln!("  PANICKED slicing the payload with the corrupted len"),
+        }
+
+        outln!();
+        outln!("Both parsers are safe Rust and see identical bytes. The validating one");
+        outln!("turns a corrupted length into an ordinary error the caller can handle;");
+        outln!("the trusting one inherits the sender's bug and crashes the receiver.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_accepts_a_pristine_frame() {
+        let wire = serialize(&Frame::new());
+        let message = deserialize(&wire).expect("pristine frame should parse");
+        assert_eq!(message.payload, &[0u8; BUFFER_SIZE]);
+        assert_eq!(message, deserialize_trusting(&wire));
+    }
+
+    #[test]
+    fn deserialize_rejects_corrupted_fields() {
+        let frame = Frame::new();
+        frame.write_len_volatile(BUFFER_SIZE as u32 + 1);
+        assert_eq!(deserialize(&serialize(&frame)), Err(WireError::LenOutOfBounds(BUFFER_SIZE as u32 + 1)));
+
+        let mut wire = serialize(&Frame::new());
+        wire[GUARD_OFF] = 0;
+        assert_eq!(deserialize(&wire), Err(WireError::GuardMismatch(0xDEAD_BE00)));
+    }
+}
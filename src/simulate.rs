@@ -0,0 +1,112 @@
+//! `--simulate`: the whole demo, modelled in safe Rust.
+//!
+//! Instead of a real `Frame` and raw pointer writes, the "frame" here is a
+//! plain `[u8; FRAME_SIZE]`. Writes are ordinary (bounds-checked) indexing into
+//! that array, and `len`/`num`/`guard` are reconstructed from its bytes with
+//! `from_le_bytes`. Because every write stays inside the struct, the dump and
+//! the outcomes match the real run byte for byte (padding included, since the
+//! real run zeroes it too), but nothing here is UB: it runs cleanly under
+//! Miri and the sanitizers. `--miri-safe` is the same mode, and `main` falls
+//! back to it by itself when built under `cfg(miri)`.
+//!
+//! It models what the unsafe code does; it doesn't do it.
+
+use crate::cli::Options;
+use crate::output::outln;
+use crate::{pause, report, runner, Frame, MemoryView, BUFFER_SIZE, BUF_OFF, FRAME_SIZE, GUARD_OFF, LEN_OFF, NUM_OFF};
+
+/// A frame as bytes, with the same initial values as `Frame::new()`.
+pub(crate) fn initial_bytes() -> [u8; FRAME_SIZE] {
+    let mut bytes = [0u8; FRAME_SIZE];
+    bytes[LEN_OFF..LEN_OFF + 4].copy_from_slice(&(BUFFER_SIZE as u32).to_le_bytes());
+    bytes[NUM_OFF..NUM_OFF + 4].copy_from_slice(&40_000i32.to_le_bytes());
+    bytes[GUARD_OFF..GUARD_OFF + 4].copy_from_slice(&0xDEAD_BEEFu32.to_le_bytes());
+    bytes
+}
+
+pub(crate) fn field_u32(bytes: &[u8; FRAME_SIZE], offset: usize) -> u32 {
+    u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+}
+
+/// `safe_sum_prefix`, over the byte model: slice the buffer by the stored `len`.
+///
+/// Only called once the recording says the consumer didn't panic, so the slice is in bounds.
+fn sum_prefix(bytes: &[u8; FRAME_SIZE]) -> u64 {
+    let len = field_u32(bytes, LEN_OFF) as usize;
+    let buffer = &bytes[BUF_OFF..BUF_OFF + BUFFER_SIZE];
+    buffer[..len].iter().map(|&b| b as u64).sum()
+}
+
+/// Run the `--writes` lengths against the model and return the outcomes.
+pub fn run(opts: &Options) -> report::RunReport {
+    outln!("=======================================================");
+    outln!("   SIMULATION: the demo on a plain byte array (no unsafe)");
+    outln!("=======================================================\n");
+    outln!("Every write below is a bounds-checked index into [u8; {FRAME_SIZE}].");
+    outln!("This MODELS what the unsafe writes do; it performs no UB.\n");
+
+    // The library runs the model; this only draws what it recorded
+    let config = runner::Config {
+        writes: opts.writes.clone(),
+        fill: opts.fill.clone(),
+        mode: runner::Mode::Simulated,
+        ..runner::Config::default()
+    };
+    let recorded = runner::record_scenario(&config);
+
+    for runner::RecordedTest { outcome, run } in &recorded {
+        let mut view: MemoryView<FRAME_SIZE> = MemoryView::for_victim::<Frame>();
+        view.apply_display_options(opts);
+        view.snapshot.copy_from_slice(&run.initial);
+
+        outln!("───────────────────────────────────────────────────────");
+        outln!("TEST: Write {} bytes starting at buffer[0] (simulated)", run.write_len);
+        outln!("───────────────────────────────────────────────────────");
+        view.print_row("init");
+
+        for step in &run.steps {
+            let prev = view.snapshot;
+            view.snapshot.copy_from_slice(&step.snapshot);
+            view.print_diff(&prev, &format!("i={}", step.index));
+            pause(opts.delay);
+        }
+
+        outln!("After:  len={}, num={}, guard=0x{:08X}", run.len, run.num, run.guard);
+        if outcome.panicked {
+            outln!("sum_prefix() PANICKED! (len was corrupted to > {BUFFER_SIZE})");
+        } else {
+            outln!("sum_prefix() = {} (len was still valid)", sum_prefix(&view.snapshot));
+        }
+        outln!();
+    }
+
+    let run_report = report::RunReport { outcomes: recorded.into_iter().map(|r| r.outcome).collect() };
+    run_report.print_report_card();
+    run_report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apply_writes;
+    use crate::victim::Victim;
+
+    #[test]
+    fn model_matches_a_real_frame_byte_for_byte() {
+        let writes: Vec<(usize, u8)> = (0..12).map(|i| (BUF_OFF + i, i as u8)).collect();
+        let mut frame = Frame::new();
+        frame.zero_padding();
+        let real = apply_writes(&mut frame, &writes);
+
+        let mut model = initial_bytes();
+        for &(offset, byte) in &writes {
+            model[offset] = byte;
+        }
+        assert_eq!(model, real);
+
+        // Padding untouched by the writes stays zeroed too
+        let mut frame = Frame::new();
+        frame.zero_padding();
+        assert_eq!(apply_writes(&mut frame, &[]), initial_bytes());
+    }
+}
@@ -0,0 +1,161 @@
+//! `--format svg`: draw the struct layout and the final corruption as an SVG.
+//!
+//! The same picture as `--tikz`, for handouts and pages that would rather
+//! have a standalone image than a TeX fragment: one cell per byte with its
+//! offset above and its final value inside, heavier borders and a labelled
+//! bracket for each field from the [`Layout`], padding greyed out, bytes that
+//! differ from the pristine frame filled red, and a legend underneath.
+
+use std::fmt::Write;
+
+use crate::layout::Layout;
+
+/// Side of one byte cell, in pixels
+const CELL: usize = 36;
+
+/// Blank space around the whole figure
+const MARGIN: usize = 20;
+
+/// Where the row of cells starts (leaves room for the title and offsets)
+const ROW_Y: usize = 56;
+
+/// Fill for bytes that changed over the run
+const CORRUPTED_FILL: &str = "#f4a6a3";
+
+/// Fill for padding bytes (not owned by any field)
+const PADDING_FILL: &str = "#e0e0e0";
+
+/// Fill for bytes that came through untouched
+const INTACT_FILL: &str = "#ffffff";
+
+/// A hand-rolled SVG document: elements are appended as text.
+struct Svg {
+    body: String,
+}
+
+impl Svg {
+    fn new() -> Self {
+        Self { body: String::new() }
+    }
+
+    fn rect(&mut self, x: usize, y: usize, width: usize, height: usize, fill: &str, stroke_width: usize) {
+        let _ = write!(self.body, r#"  <rect x="{x}" y="{y}" width="{width}" height="{height}" fill="{fill}" "#);
+        let _ = writeln!(self.body, r##"stroke="#000" stroke-width="{stroke_width}"/>"##);
+    }
+
+    /// Text positioned by `anchor`, an SVG `text-anchor` (`start` or `middle`)
+    fn text(&mut self, x: usize, y: usize, size: usize, anchor: &str, color: &str, text: &str) {
+        let _ = writeln!(
+            self.body,
+            r#"  <text x="{x}" y="{y}" font-size="{size}" text-anchor="{anchor}" fill="{color}">{}</text>"#,
+            escape(text)
+        );
+    }
+
+    fn polyline(&mut self, points: &[(usize, usize)]) {
+        let points: Vec<String> = points.iter().map(|(x, y)| format!("{x},{y}")).collect();
+        let _ = writeln!(
+            self.body,
+            r##"  <polyline points="{}" fill="none" stroke="#000" stroke-width="1.5"/>"##,
+            points.join(" ")
+        );
+    }
+
+    fn finish(self, width: usize, height: usize) -> String {
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\" font-family=\"ui-monospace, Menlo, Consolas, monospace\">\n\
+             {}</svg>\n",
+            self.body
+        )
+    }
+}
+
+/// Render `layout` with the bytes of `last` that differ from `initial` filled red.
+pub fn render(layout: &Layout, initial: &[u8], last: &[u8]) -> String {
+    let mut svg = Svg::new();
+    let x_of = |offset: usize| MARGIN + offset * CELL;
+    let width = x_of(last.len()) + MARGIN;
+
+    svg.text(
+        MARGIN,
+        MARGIN + 4,
+        14,
+        "start",
+        "#000",
+        &format!("{} ({}), {} bytes: final state", layout.name, layout.repr, layout.size),
+    );
+
+    for (offset, (&before, &after)) in initial.iter().zip(last).enumerate() {
+        let fill = if before != after {
+            CORRUPTED_FILL
+        } else if layout.field_index_at(offset).is_none() {
+            PADDING_FILL
+        } else {
+            INTACT_FILL
+        };
+        svg.rect(x_of(offset), ROW_Y, CELL, CELL, fill, 1);
+        svg.text(x_of(offset) + CELL / 2, ROW_Y + CELL / 2 + 5, 14, "middle", "#000", &format!("{after:02x}"));
+        svg.text(x_of(offset) + CELL / 2, ROW_Y - 6, 10, "middle", "#888", &offset.to_string());
+    }
+
+    // Field boundaries: a heavier outline per field, with a labelled bracket below
+    let bracket_y = ROW_Y + CELL + 8;
+    for field in layout.fields {
+        let (start, end) = (x_of(field.offset), x_of(field.end()));
+        svg.rect(start, ROW_Y, end - start, CELL, "none", 3);
+        let (left, right) = (start + 3, end - 3);
+        svg.polyline(&[(left, bracket_y - 5), (left, bracket_y), (right, bracket_y), (right, bracket_y - 5)]);
+        svg.text((start + end) / 2, bracket_y + 16, 12, "middle", "#000", field.name);
+    }
+
+    // Legend: one swatch per fill
+    let legend_y = bracket_y + 36;
+    let mut x = MARGIN;
+    for (fill, label) in [
+        (CORRUPTED_FILL, "changed during the run"),
+        (PADDING_FILL, "padding"),
+        (INTACT_FILL, "untouched"),
+    ] {
+        svg.rect(x, legend_y, 14, 14, fill, 1);
+        svg.text(x + 20, legend_y + 11, 12, "start", "#000", label);
+        x += 20 + label.len() * 8 + 24;
+    }
+
+    svg.finish(width.max(x + MARGIN), legend_y + 14 + MARGIN)
+}
+
+/// Escape the characters that mean something in XML text.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::FieldInfo;
+
+    #[test]
+    fn changed_bytes_and_padding_get_their_own_fills() {
+        static LAYOUT: Layout = Layout {
+            name: "Pair",
+            repr: "repr(C)",
+            size: 4,
+            align: 2,
+            fields: &[
+                FieldInfo { name: "flag", offset: 0, size: 1, align: 1 },
+                FieldInfo { name: "n<2>", offset: 2, size: 2, align: 2 },
+            ],
+        };
+        let svg = render(&LAYOUT, &[0, 0, 5, 0], &[0, 0, 9, 0]);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        let padding_cell =
+            format!("x=\"{}\" y=\"{ROW_Y}\" width=\"{CELL}\" height=\"{CELL}\" fill=\"{PADDING_FILL}\"", MARGIN + CELL);
+        assert!(svg.contains(&padding_cell));
+        assert!(svg.contains(&format!("fill=\"{CORRUPTED_FILL}\"")));
+        assert!(svg.contains(">09</text>"));
+        assert!(svg.contains(">n&lt;2&gt;</text>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
@@ -0,0 +1,62 @@
+//! `--tikz`: draw the struct layout and the final corruption as a TikZ figure.
+//!
+//! The output is a bare `tikzpicture` meant to be `\input` into a document
+//! that loads TikZ. Each byte is one cell with its offset above and its final
+//! value inside; fields are bracketed and labelled underneath from the
+//! [`Layout`], padding is greyed out, and bytes that differ from the pristine
+//! frame are shaded. The field brackets need
+//! `\usetikzlibrary{decorations.pathreplacing}` in the preamble.
+
+use std::fmt::Write;
+
+use crate::layout::Layout;
+
+/// Fill for bytes that changed over the run
+const CORRUPTED_FILL: &str = "red!35";
+
+/// Fill for padding bytes (not owned by any field)
+const PADDING_FILL: &str = "black!10";
+
+/// Render `layout` with the bytes of `last` that differ from `initial` shaded.
+pub fn render(layout: &Layout, initial: &[u8], last: &[u8]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "% {} ({}), {} bytes; generated by rust-uaf --tikz", layout.name, layout.repr, layout.size);
+    let _ = writeln!(out, "% Shaded cells changed during the run; grey cells are padding.");
+    out.push_str("\\begin{tikzpicture}[x=7mm, y=7mm, font=\\ttfamily\\scriptsize]\n");
+
+    for (offset, (&before, &after)) in initial.iter().zip(last).enumerate() {
+        let fill = if before != after {
+            CORRUPTED_FILL
+        } else if layout.field_index_at(offset).is_none() {
+            PADDING_FILL
+        } else {
+            "white"
+        };
+        let _ = writeln!(
+            out,
+            "  \\draw[fill={fill}] ({offset},0) rectangle +(1,1) node[midway] {{{after:02x}}};"
+        );
+        let _ = writeln!(out, "  \\node[above, black!60] at ({:.1},1) {{{offset}}};", offset as f32 + 0.5);
+    }
+
+    // Field boundaries: heavier rules between fields, with a labelled bracket below
+    for field in layout.fields {
+        let (start, end) = (field.offset, field.end());
+        let _ = writeln!(out, "  \\draw[very thick] ({start},0) rectangle ({end},1);");
+        let _ = writeln!(
+            out,
+            "  \\draw[decorate, decoration={{brace, mirror, amplitude=3pt}}] ({:.1},-0.1) -- ({:.1},-0.1)",
+            start as f32 + 0.1,
+            end as f32 - 0.1,
+        );
+        let _ = writeln!(out, "    node[midway, below=3pt] {{{}}};", escape(field.name));
+    }
+
+    out.push_str("\\end{tikzpicture}\n");
+    out
+}
+
+/// Escape the characters TeX treats specially in field names.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\textbackslash{}").replace('_', "\\_").replace('&', "\\&")
+}
@@ -0,0 +1,196 @@
+//! `--save-trace` and `--replay`: a whole run as one portable JSON file.
+//!
+//! The file is `{"buffer_size": B, "tests": [...]}`, where each test is the
+//! same [`TestRun`] object `--format json` prints: its initial frame, every
+//! write with the snapshot it left behind, and the final outcome. Alongside
+//! go the choices that print something of their own (the random seed and
+//! what it drew, and the write direction), so the replay says what the
+//! original run said. Replaying
+//! copies those recorded snapshots over a fresh frame instead of writing past
+//! the buffer, so a graded or shared run renders exactly as it first did
+//! without any out-of-bounds write.
+
+use std::path::Path;
+
+use crate::cli::Direction;
+use crate::demo;
+use crate::json::{self, Object, ToJson, Value};
+use crate::layout::Layout;
+use crate::report::{TestRun, WriteStep};
+use crate::MAX_BUFFER_SIZE;
+
+/// A recorded run: the buffer size it used, how it was set up, and every test, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    pub buffer_size: usize,
+
+    /// Whether each frame started from random bytes (`--random-init`)
+    pub random_init: bool,
+
+    /// Whether the writes stored random bytes (`--fill random`)
+    pub random_fill: bool,
+
+    /// The seed behind either of the above; `None` when neither was used
+    pub seed: Option<u64>,
+
+    /// Which end of each test's range was written first
+    pub direction: Direction,
+
+    pub tests: Vec<TestRun>,
+}
+
+impl ToJson for Trace {
+    fn write_json(&self, out: &mut String) {
+        // As a string: a seed can be any u64, and JSON numbers here are i64
+        let seed = self.seed.map(|seed| seed.to_string());
+        Object::new()
+            .field("buffer_size", self.buffer_size)
+            .field("random_init", self.random_init)
+            .field("random_fill", self.random_fill)
+            .field("seed", seed)
+            .field("direction", if self.direction == Direction::Reverse { "reverse" } else { "forward" })
+            .field("tests", &self.tests)
+            .write_json(out);
+    }
+}
+
+impl Trace {
+    /// Write the trace as one line of JSON.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let mut text = String::new();
+        self.write_json(&mut text);
+        text.push('\n');
+        std::fs::write(path, text)
+    }
+
+    /// Read a trace back from `path`.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        Self::parse(&std::fs::read_to_string(path).map_err(|err| err.to_string())?)
+    }
+
+    /// Parse a trace, checking that every snapshot is one frame of the recorded buffer size.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let root = json::parse(text).map_err(|err| format!("not valid JSON: {err}"))?;
+
+        let buffer_size = uint(&root, "buffer_size")?;
+        if !(1..=MAX_BUFFER_SIZE).contains(&buffer_size) {
+            return Err(format!("buffer_size {buffer_size} is outside 1..={MAX_BUFFER_SIZE}"));
+        }
+        let (layout, size) = (demo::layout(buffer_size), demo::frame_size(buffer_size));
+
+        // Traces from before these were recorded replay as a plain forward run
+        let random_init = flag(&root, "random_init")?;
+        let random_fill = flag(&root, "random_fill")?;
+        let seed = match root.get("seed") {
+            None | Some(Value::Null) => None,
+            Some(seed) => Some(seed.as_str().and_then(|s| s.parse().ok()).ok_or("`seed` is not a u64 string")?),
+        };
+        let direction = match root.get("direction").map(|d| d.as_str()) {
+            None | Some(Some("forward")) => Direction::Forward,
+            Some(Some("reverse")) => Direction::Reverse,
+            Some(_) => return Err("`direction` is not \"forward\" or \"reverse\"".into()),
+        };
+
+        let tests = array(&root, "tests")?
+            .iter()
+            .enumerate()
+            .map(|(i, test)| test_run(test, layout, size).map_err(|err| format!("test {i}: {err}")))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { buffer_size, random_init, random_fill, seed, direction, tests })
+    }
+}
+
+fn member<'a>(value: &'a Value, key: &str) -> Result<&'a Value, String> {
+    value.get(key).ok_or_else(|| format!("missing `{key}`"))
+}
+
+fn array<'a>(value: &'a Value, key: &str) -> Result<&'a [Value], String> {
+    member(value, key)?.as_array().ok_or_else(|| format!("`{key}` is not an array"))
+}
+
+fn int(value: &Value, key: &str) -> Result<i64, String> {
+    member(value, key)?.as_i64().ok_or_else(|| format!("`{key}` is not an integer"))
+}
+
+/// An optional boolean member, `false` when absent
+fn flag(value: &Value, key: &str) -> Result<bool, String> {
+    value.get(key).map_or(Ok(false), |v| v.as_bool().ok_or_else(|| format!("`{key}` is not a boolean")))
+}
+
+fn uint(value: &Value, key: &str) -> Result<usize, String> {
+    usize::try_from(int(value, key)?).map_err(|_| format!("`{key}` is negative"))
+}
+
+/// A frame's bytes, which must be exactly `size` of them
+fn frame_bytes(value: &Value, key: &str, size: usize) -> Result<Vec<u8>, String> {
+    let bytes = array(value, key)?
+        .iter()
+        .map(|b| b.as_i64().and_then(|b| u8::try_from(b).ok()))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| format!("`{key}` holds something other than bytes"))?;
+    if bytes.len() != size {
+        return Err(format!("`{key}` holds {} bytes, but the frame is {size}", bytes.len()));
+    }
+    Ok(bytes)
+}
+
+fn test_run(value: &Value, layout: &'static Layout, size: usize) -> Result<TestRun, String> {
+    let initial = frame_bytes(value, "initial", size)?;
+    let mut run = TestRun::new(uint(value, "test")?, uint(value, "write_len")?, layout, &initial);
+    for step in array(value, "steps")? {
+        let changed = array(step, "changed")?
+            .iter()
+            .map(|c| c.as_i64().and_then(|c| usize::try_from(c).ok()))
+            .collect::<Option<Vec<usize>>>()
+            .ok_or("`changed` holds something other than offsets")?;
+        run.steps.push(WriteStep {
+            index: uint(step, "index")?,
+            offset: uint(step, "offset")?,
+            value: u8::try_from(int(step, "value")?).map_err(|_| "`value` is not a byte")?,
+            snapshot: frame_bytes(step, "snapshot", size)?,
+            changed,
+        });
+    }
+    // Fewer steps is fine: a --step run can be stopped early
+    if run.steps.len() > run.write_len {
+        return Err(format!("{} steps recorded for a {}-byte write", run.steps.len(), run.write_len));
+    }
+    run.len = int(value, "len")? as u32;
+    run.num = int(value, "num")? as i32;
+    run.guard = int(value, "guard")? as u32;
+    run.panicked = member(value, "panicked")?.as_bool().ok_or("`panicked` is not a boolean")?;
+    Ok(run)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Frame, BUFFER_SIZE, FRAME_SIZE};
+
+    #[test]
+    fn a_saved_trace_parses_back_to_the_same_runs() {
+        let initial = [0u8; FRAME_SIZE];
+        let mut after = initial;
+        after[0] = 0xAB;
+        let mut run = TestRun::new(0, 2, Frame::LAYOUT, &initial);
+        run.steps.push(WriteStep { index: 0, offset: 0, value: 0xAB, snapshot: after.to_vec(), changed: vec![0] });
+        run.num = -1;
+        run.guard = 0xDEAD_BEEF;
+        let trace = Trace {
+            buffer_size: BUFFER_SIZE,
+            random_init: false,
+            random_fill: true,
+            seed: Some(u64::MAX),
+            direction: Direction::Reverse,
+            tests: vec![run],
+        };
+
+        let mut text = String::new();
+        trace.write_json(&mut text);
+        assert_eq!(Trace::parse(&text), Ok(trace));
+
+        let short = text.replacen("\"snapshot\":[171,", "\"snapshot\":[", 1);
+        let err = format!("test 0: `snapshot` holds 19 bytes, but the frame is {FRAME_SIZE}");
+        assert_eq!(Trace::parse(&short), Err(err));
+    }
+}
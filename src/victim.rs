@@ -0,0 +1,199 @@
+//! The [`Victim`] trait: what the hex dump needs to know about a struct.
+//!
+//! The demo's dump, its watched fields, and its layout printout only need a
+//! pointer to the struct and a description of where things live in it.
+//! Anything that can answer those questions can be overflowed and shown the
+//! same way `Frame` is; `SizedFrame` is the implementation the demo uses.
+
+use std::mem::size_of;
+
+use crate::layout::Layout;
+use crate::output::outln;
+use crate::scenarios::ptr_field::PtrFrame;
+use crate::{field_boundaries, MemoryView, MemoryViewBuilder, SizedFrame, GUARD_SENTINEL, MAX_BUFFER_SIZE};
+
+/// A `#[repr(C)]` struct with a buffer that the demo can write past.
+pub trait Victim: Sized {
+    /// Where the unchecked writes and the dump start
+    fn base_ptr(&mut self) -> *mut u8 {
+        (self as *mut Self).cast::<u8>()
+    }
+
+    /// How many bytes the dump shows
+    fn frame_size() -> usize {
+        size_of::<Self>()
+    }
+
+    /// Byte ranges to highlight until they're corrupted
+    fn watched_ranges() -> &'static [(usize, usize)];
+
+    /// Offsets to draw a `|` before: where the buffer ends and where each
+    /// watched range starts, unless the struct says otherwise
+    fn separators() -> Vec<usize> {
+        let buffer_end = Self::buffer_offset() + Self::buffer_len();
+        let starts = Self::watched_ranges().iter().map(|&(start, _)| start);
+        field_boundaries(starts.chain([buffer_end]), Self::frame_size())
+    }
+
+    /// Byte ranges that belong to no field; none unless the struct says so
+    fn padding_ranges() -> &'static [(usize, usize)] {
+        &[]
+    }
+
+    /// A `u32` canary's offset and the value it should hold; none unless the struct has one
+    fn sentinel() -> Option<(usize, u32)> {
+        None
+    }
+
+    /// Every field, for the layout printout and `--field-colors`
+    fn describe_fields() -> &'static Layout;
+
+    /// Offset of the buffer the writes start at
+    fn buffer_offset() -> usize;
+
+    /// How many bytes the buffer actually holds; writes past this corrupt
+    fn buffer_len() -> usize;
+
+    /// Zero the padding bytes, which no constructor writes, so a dump of the
+    /// struct shows the same bytes every run instead of whatever was there.
+    ///
+    /// Call it once the struct is where it stays: a move copies padding as
+    /// uninitialized again.
+    fn zero_padding(&mut self) {
+        let base_ptr = self.base_ptr();
+        for &(start, end) in Self::padding_ranges() {
+            // Padding lies inside the struct and no field reads it
+            unsafe { std::ptr::write_bytes(base_ptr.add(start), 0, end - start) };
+        }
+    }
+}
+
+impl<const B: usize> Victim for SizedFrame<B> {
+    fn watched_ranges() -> &'static [(usize, usize)] {
+        Self::WATCHED
+    }
+
+    fn padding_ranges() -> &'static [(usize, usize)] {
+        Self::PADDING
+    }
+
+    fn sentinel() -> Option<(usize, u32)> {
+        Some((Self::guard_offset(), GUARD_SENTINEL))
+    }
+
+    fn describe_fields() -> &'static Layout {
+        Self::LAYOUT
+    }
+
+    fn buffer_offset() -> usize {
+        SizedFrame::<B>::buffer_offset()
+    }
+
+    fn buffer_len() -> usize {
+        B
+    }
+}
+
+impl<const N: usize> MemoryView<N> {
+    /// A view of `V`, watching, separating, and graying out what `V` asks for,
+    /// and checking its sentinel if it has one.
+    pub fn for_victim<V: Victim>() -> Self {
+        assert_eq!(V::frame_size(), N, "a MemoryView<{N}> can't show a {}-byte victim", V::frame_size());
+        let mut view = MemoryViewBuilder::new()
+            .watched_ranges(V::watched_ranges())
+            .separators(&V::separators())
+            .build();
+        view.padding_ranges = V::padding_ranges();
+        view.sentinel = V::sentinel();
+        view.field_names = Some(V::describe_fields());
+        view
+    }
+}
+
+/// Panic unless `V`'s watched ranges and separators can be drawn faithfully.
+///
+/// Checks that every watched range starts at or after the end of the buffer
+/// and stays inside the frame, that no two watched ranges overlap, and that
+/// every separator is within `0..=frame_size()`. None of these make anything
+/// fail on their own; they just draw a wrong picture, which is easy to miss
+/// after adding a field. Debug builds of the binary check every victim at
+/// startup, through [`validate_all_layouts`].
+///
+/// # Panics
+///
+/// On the first violation, naming the offending range or separator.
+pub fn validate_layout<V: Victim>() {
+    let name = V::describe_fields().name;
+    let size = V::frame_size();
+    let buf_end = V::buffer_offset() + V::buffer_len();
+    let watched = V::watched_ranges();
+    for (index, &(start, end)) in watched.iter().enumerate() {
+        assert!(
+            start >= buf_end,
+            "{name}: watched range {start}..{end} starts inside the buffer, which ends at {buf_end}"
+        );
+        assert!(end <= size, "{name}: watched range {start}..{end} runs past the end of the {size}-byte frame");
+        for &(other_start, other_end) in &watched[..index] {
+            assert!(
+                end <= other_start || other_end <= start,
+                "{name}: watched ranges {other_start}..{other_end} and {start}..{end} overlap"
+            );
+        }
+    }
+    for offset in V::separators() {
+        assert!(offset <= size, "{name}: separator at {offset} is outside 0..={size}");
+    }
+}
+
+/// `validate_layout` for every `Victim` in the crate: `SizedFrame` at each
+/// `--buffer-size` (`Frame` among them) and the `--scenario ptr` frame.
+pub fn validate_all_layouts() {
+    for buffer_size in 1..=MAX_BUFFER_SIZE {
+        with_buffer_size!(buffer_size, validate_sized());
+    }
+    validate_layout::<PtrFrame>();
+}
+
+/// `validate_layout` for `SizedFrame<B>`; `N` is only there for `with_buffer_size!`.
+fn validate_sized<const B: usize, const N: usize>() {
+    validate_layout::<SizedFrame<B>>();
+}
+
+/// Print where each of `V`'s fields lives, one line per field in memory order,
+/// with each field's alignment and every padding gap that alignment forced.
+pub fn print_layout<V: Victim>() {
+    let layout = V::describe_fields();
+    outln!("{} struct layout (all offsets in bytes, align_of::<{}>() = {}):", layout.name, layout.name, layout.align);
+    let mut cursor = 0;
+    for field in layout.fields_by_offset() {
+        if field.offset > cursor {
+            let why = format!("{} needs {}-byte alignment, so it can't start at {cursor}", field.name, field.align);
+            print_padding(cursor, field.offset, &why);
+        }
+        let name = format!("{}:", field.name);
+        outln!(
+            "  {name:<7} [{}..{}), size = {} bytes, align = {}",
+            field.offset,
+            field.end(),
+            field.size,
+            field.align
+        );
+        cursor = cursor.max(field.end());
+    }
+    if layout.size > cursor {
+        let why = format!("rounds the size up to a multiple of {}", layout.align);
+        print_padding(cursor, layout.size, &why);
+    }
+
+    match layout.padding_bytes() {
+        0 => outln!("  No padding: every field starts right where the one before it ends."),
+        n => outln!("  {n} bytes of padding in all"),
+    }
+    outln!("  Total {} size = {} bytes\n", layout.name, V::frame_size());
+}
+
+/// One `(padding)` line of the layout printout
+fn print_padding(start: usize, end: usize, why: &str) {
+    let bytes = if end - start == 1 { "byte" } else { "bytes" };
+    outln!("  {:<7} [{start}..{end}), {} {bytes} of padding: {why}", "(pad)", end - start);
+}
@@ -0,0 +1,75 @@
+//! The binary itself, run the way a student would run it.
+
+use std::path::Path;
+use std::process::Command;
+
+/// Run the demo with `args`, returning its stdout.
+fn run(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-uaf"))
+        .args(args)
+        .env("NO_COLOR", "1")
+        .env_remove("CLICOLOR_FORCE")
+        .output()
+        .expect("the demo binary runs");
+    String::from_utf8(output.stdout).expect("the output is UTF-8")
+}
+
+/// Run the demo with arguments it should reject, returning the first line of its stderr.
+fn rejected(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-uaf")).args(args).output().expect("the demo binary runs");
+    assert_eq!(output.status.code(), Some(2), "`{}` was accepted", args.join(" "));
+    let stderr = String::from_utf8(output.stderr).expect("the errors are UTF-8");
+    stderr.lines().next().unwrap_or_default().to_string()
+}
+
+#[test]
+fn a_replay_prints_what_the_recorded_run_printed() {
+    let path = std::env::temp_dir().join(format!("rust-uaf-replay-{}.json", std::process::id()));
+    let trace = path.to_str().unwrap();
+
+    for args in [
+        &["--fill", "random", "--seed", "5", "--writes", "6"][..],
+        &["--random-init", "--seed", "3", "--direction", "reverse", "--writes", "6,12"],
+    ] {
+        let original = run(&[args, &["--save-trace", trace]].concat());
+        let replayed = run(&["--replay", trace]);
+
+        // Saving the trace is the one thing only the original run reports
+        let tests = args.last().unwrap().split(',').count();
+        let saved = format!("\nWrote a trace of {tests} tests to {trace} (play it back with --replay)\n");
+        assert!(original.contains(&saved), "no trace was saved:\n{original}");
+        assert!(replayed.contains("Random "), "the seed banner is missing:\n{replayed}");
+        assert_eq!(original.replacen(&saved, "", 1), replayed);
+    }
+    let _ = std::fs::remove_file(Path::new(trace));
+}
+
+#[test]
+fn fill_values_are_hex_with_or_without_0x() {
+    for fill in [["--fill-value", "41"], ["--fill-value", "0x41"], ["--fill", "const:41"], ["--fill", "const:0x41"]] {
+        let output = run(&[&fill[..], &["--writes", "12"]].concat());
+        assert!(output.contains("After:  len=1094795585,"), "`{}` didn't write 0x41:\n{output}", fill.join(" "));
+    }
+}
+
+#[test]
+fn bad_ranges_and_length_lists_are_rejected() {
+    assert_eq!(rejected(&["--sweep", "0..0"]), "error: `0..0` is an empty range");
+    assert_eq!(rejected(&["--sweep", "5..3"]), "error: `5..3` is an empty range");
+    assert_eq!(rejected(&["--sweep", "5"]), "error: `5` is not a range (expected e.g. 0..16 or 0..=16)");
+    assert_eq!(rejected(&["--writes", "5,,6"]), "error: `5,,6` has an empty length between its commas");
+    assert_eq!(rejected(&["--writes", ""]), "error: `--writes` needs at least one length (e.g. 5,6,8,20)");
+    assert_eq!(rejected(&["--writes", "5,six"]), "error: `six` is not a valid number: invalid digit found in string");
+}
+
+#[test]
+fn ranges_and_length_lists_pick_the_tests() {
+    let sweep = run(&["--sweep", "4..=6"]);
+    let lengths: Vec<&str> = sweep.lines().filter_map(|line| line.split_whitespace().next()).collect();
+    assert!(lengths.windows(3).any(|w| w == ["4", "5", "6"]), "the sweep doesn't run 4, 5, 6:\n{sweep}");
+    assert!(!lengths.contains(&"7"), "the sweep ran past 6:\n{sweep}");
+
+    let writes = run(&["--writes", "5, 0x6"]);
+    let tests: Vec<&str> = writes.lines().filter(|line| line.starts_with("TEST: Write ")).collect();
+    assert_eq!(tests, ["TEST: Write 5 bytes starting at buffer[0]", "TEST: Write 6 bytes starting at buffer[0]"]);
+}
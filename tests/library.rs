@@ -0,0 +1,159 @@
+//! The public API, used the way an outside crate (or a course exercise) would.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use rust_uaf::layout::{FieldInfo, Layout};
+use rust_uaf::victim::{validate_all_layouts, validate_layout, Victim};
+use rust_uaf::{safe_sum_prefix, Frame, MemoryView, SizedFrame, BUFFER_SIZE, FRAME_SIZE};
+
+#[test]
+fn fields_follow_the_buffer_in_declaration_order() {
+    assert_eq!(Frame::buffer_offset(), 0);
+    assert!(Frame::len_offset() >= Frame::buffer_offset() + BUFFER_SIZE);
+    assert!(Frame::num_offset() > Frame::len_offset());
+    assert!(Frame::guard_offset() > Frame::num_offset());
+    assert!(Frame::guard_offset() + 4 <= FRAME_SIZE);
+}
+
+#[test]
+fn field_sizes_tile_the_frame_without_overlap() {
+    assert_eq!(Frame::buffer_size(), BUFFER_SIZE);
+    assert!(Frame::buffer_offset() + Frame::buffer_size() <= Frame::len_offset());
+    assert_eq!(Frame::len_offset() + Frame::len_size(), Frame::num_offset());
+    assert_eq!(Frame::num_offset() + Frame::num_size(), Frame::guard_offset());
+    assert!(Frame::guard_offset() + Frame::guard_size() <= Frame::frame_size());
+    assert_eq!(Frame::frame_size(), FRAME_SIZE);
+    assert_eq!(SizedFrame::<9>::buffer_size(), 9);
+    assert!(SizedFrame::<9>::len_offset() >= SizedFrame::<9>::buffer_size());
+}
+
+#[test]
+fn a_fresh_frame_satisfies_the_invariant() {
+    let frame = Frame::new();
+    assert_eq!(frame.read_len_volatile(), BUFFER_SIZE as u32);
+    assert_eq!(frame.read_guard_volatile(), 0xDEAD_BEEF);
+    assert_eq!(safe_sum_prefix(&frame), 0);
+}
+
+#[test]
+fn overwriting_len_breaks_safe_code() {
+    let mut frame = Frame::new();
+    let base_ptr: *mut u8 = (&mut frame as *mut Frame).cast::<u8>();
+    unsafe { *base_ptr.add(Frame::len_offset()) = BUFFER_SIZE as u8 + 1 };
+
+    assert_eq!(frame.read_len_volatile(), BUFFER_SIZE as u32 + 1);
+    assert!(catch_unwind(AssertUnwindSafe(|| safe_sum_prefix(&frame))).is_err());
+}
+
+#[test]
+fn memory_view_reads_fields_back_from_its_snapshot() {
+    let mut view: MemoryView<8> = MemoryView::new(&[(4, 8)], &[4]);
+    view.snapshot = [0, 0, 0, 0, 0xEF, 0xBE, 0xAD, 0xDE];
+
+    assert_eq!(view.read_u32(4), 0xDEAD_BEEF);
+    assert!(view.is_watched(4) && !view.is_watched(3));
+}
+
+#[test]
+fn watched_ranges_and_separators_follow_the_buffer_size() {
+    type Wide = SizedFrame<8>;
+
+    // No padding at 8 bytes: len starts right where the buffer ends
+    assert_eq!(Wide::len_offset(), 8);
+    assert_eq!(Wide::WATCHED[0], (8, 12));
+    assert_eq!(Wide::separators(), [8, 12, 16]);
+    assert_eq!(Wide::LAYOUT.fields[0].size, 8);
+    assert_eq!(Wide::new().read_len_volatile(), 8);
+}
+
+#[test]
+fn a_new_victim_plugs_into_memory_view() {
+    #[repr(C)]
+    struct Flagged {
+        buffer: [u8; 3],
+        admin: bool,
+    }
+
+    static LAYOUT: Layout = Layout {
+        name: "Flagged",
+        repr: "repr(C)",
+        size: 4,
+        align: 1,
+        fields: &[
+            FieldInfo { name: "buffer", offset: 0, size: 3, align: 1 },
+            FieldInfo { name: "admin", offset: 3, size: 1, align: 1 },
+        ],
+    };
+
+    impl Victim for Flagged {
+        fn watched_ranges() -> &'static [(usize, usize)] {
+            &[(3, 4)]
+        }
+        fn describe_fields() -> &'static Layout {
+            &LAYOUT
+        }
+        fn buffer_offset() -> usize {
+            0
+        }
+        fn buffer_len() -> usize {
+            3
+        }
+    }
+
+    let mut victim = Flagged { buffer: [0; 3], admin: false };
+    let base_ptr = victim.base_ptr();
+    unsafe { *base_ptr.add(Flagged::buffer_offset() + Flagged::buffer_len()) = 1 };
+
+    let mut view: MemoryView<4> = MemoryView::for_victim::<Flagged>();
+    unsafe { std::ptr::copy_nonoverlapping(base_ptr, view.snapshot.as_mut_ptr(), 4) };
+    assert!(view.is_watched(3));
+    assert_eq!(view.snapshot, [0, 0, 0, 1]);
+    assert!(victim.admin && victim.buffer == [0; 3]);
+}
+
+#[test]
+fn every_victim_lays_out_cleanly() {
+    validate_all_layouts();
+}
+
+#[test]
+#[should_panic(expected = "watched ranges 4..8 and 6..10 overlap")]
+fn overlapping_watched_ranges_are_caught() {
+    /// A 4-byte buffer and two `u32`s, with a second watched range that straddles `len` and `num`
+    #[repr(C)]
+    #[allow(dead_code)] // only its layout matters
+    struct Misdrawn {
+        buffer: [u8; 4],
+        len: u32,
+        num: u32,
+    }
+
+    static LAYOUT: Layout = Layout {
+        name: "Misdrawn",
+        repr: "repr(C)",
+        size: 12,
+        align: 4,
+        fields: &[
+            FieldInfo { name: "buffer", offset: 0, size: 4, align: 1 },
+            FieldInfo { name: "len", offset: 4, size: 4, align: 4 },
+            FieldInfo { name: "num", offset: 8, size: 4, align: 4 },
+        ],
+    };
+
+    impl Victim for Misdrawn {
+        fn watched_ranges() -> &'static [(usize, usize)] {
+            &[(4, 8), (6, 10)]
+        }
+        fn describe_fields() -> &'static Layout {
+            &LAYOUT
+        }
+        fn buffer_offset() -> usize {
+            0
+        }
+        fn buffer_len() -> usize {
+            4
+        }
+    }
+
+    validate_layout::<Misdrawn>();
+}